@@ -1,5 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use fastdiv::FastDiv;
+use fastdiv::{
+    BranchFreeDivU32, BranchFreeDivU64, FastDiv, MagicDivU64, PrecomputedDivisibilityU64,
+};
 
 pub fn criterion_benchmark(c: &mut Criterion) {
     {
@@ -22,6 +24,32 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         c.bench_function("const mod u32", |b| {
             b.iter(|| black_box(black_box(n) % 3))
         });
+
+        let branchfree = BranchFreeDivU32::new(d);
+
+        c.bench_function("branchfree div u32", |b| {
+            b.iter(|| black_box(branchfree.div(black_box(n))))
+        });
+
+        let pow2: u32 = black_box(4);
+        let precomputed_pow2 = pow2.precompute_div();
+
+        c.bench_function("fast div pow2 u32", |b| {
+            b.iter(|| black_box(black_box(n).fast_div(precomputed_pow2)))
+        });
+        c.bench_function("fast mod pow2 u32", |b| {
+            b.iter(|| black_box(black_box(n).fast_mod(precomputed_pow2, pow2)))
+        });
+
+        c.bench_function("fast div_rem u32", |b| {
+            b.iter(|| black_box(black_box(n).fast_div_rem(precomputed, d)))
+        });
+        c.bench_function("fast div + mod u32", |b| {
+            b.iter(|| {
+                let n = black_box(n);
+                black_box((n.fast_div(precomputed), n.fast_mod(precomputed, d)))
+            })
+        });
     }
     {
         let d: u64 = black_box(3);
@@ -43,8 +71,152 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         c.bench_function("const mod u64", |b| {
             b.iter(|| black_box(black_box(n) % 3))
         });
+
+        let magic = MagicDivU64::new(d);
+
+        c.bench_function("magic div u64", |b| {
+            b.iter(|| black_box(magic.div(black_box(n))))
+        });
+        c.bench_function("magic mod u64", |b| {
+            b.iter(|| black_box(magic.rem(black_box(n), d)))
+        });
+
+        let divisibility = PrecomputedDivisibilityU64::new(d);
+
+        c.bench_function("fast is_multiple_of u64", |b| {
+            b.iter(|| black_box(FastDiv::is_multiple_of(black_box(n), precomputed)))
+        });
+        c.bench_function("inverse is_multiple_of u64", |b| {
+            b.iter(|| black_box(divisibility.is_multiple_of(black_box(n))))
+        });
+
+        let branchfree = BranchFreeDivU64::new(d);
+
+        c.bench_function("branchfree div u64", |b| {
+            b.iter(|| black_box(branchfree.div(black_box(n))))
+        });
+
+        let pow2: u64 = black_box(4);
+        let precomputed_pow2 = pow2.precompute_div();
+
+        c.bench_function("fast div pow2 u64", |b| {
+            b.iter(|| black_box(black_box(n).fast_div(precomputed_pow2)))
+        });
+        c.bench_function("fast mod pow2 u64", |b| {
+            b.iter(|| black_box(black_box(n).fast_mod(precomputed_pow2, pow2)))
+        });
+
+        c.bench_function("fast div_rem u64", |b| {
+            b.iter(|| black_box(black_box(n).fast_div_rem(precomputed, d)))
+        });
+        c.bench_function("fast div + mod u64", |b| {
+            b.iter(|| {
+                let n = black_box(n);
+                black_box((n.fast_div(precomputed), n.fast_mod(precomputed, d)))
+            })
+        });
+    }
+    {
+        let d: u128 = black_box(3);
+        let n: u128 = 2_u128.pow(64);
+        let precomputed = d.precompute_div();
+
+        c.bench_function("fast div u128", |b| {
+            b.iter(|| black_box(black_box(n).fast_div(precomputed)))
+        });
+        c.bench_function("slow div u128", |b| b.iter(|| black_box(black_box(n) / d)));
+        c.bench_function("const div u128", |b| {
+            b.iter(|| black_box(black_box(n) / 3))
+        });
+
+        c.bench_function("fast mod u128", |b| {
+            b.iter(|| black_box(black_box(n).fast_mod(precomputed, d)))
+        });
+        c.bench_function("slow mod u128", |b| b.iter(|| black_box(black_box(n) % d)));
+        c.bench_function("const mod u128", |b| {
+            b.iter(|| black_box(black_box(n) % 3))
+        });
+    }
+}
+
+pub fn slice_benchmark(c: &mut Criterion) {
+    {
+        let d: u32 = black_box(7);
+        let precomputed = d.precompute_div();
+        let src: Vec<u32> = (0..1 << 16).map(|i: u32| i.wrapping_mul(2654435761)).collect();
+        let mut dst = vec![0; src.len()];
+
+        c.bench_function("fast div slice u32", |b| {
+            b.iter(|| u32::fast_div_slice_into(black_box(&src), &mut dst, precomputed))
+        });
+        c.bench_function("slow div slice u32", |b| {
+            b.iter(|| {
+                for (dst, src) in dst.iter_mut().zip(black_box(&src)) {
+                    *dst = src / d;
+                }
+            })
+        });
+        c.bench_function("fast div loop u32", |b| {
+            b.iter(|| {
+                for (dst, src) in dst.iter_mut().zip(black_box(&src)) {
+                    *dst = src.fast_div(precomputed);
+                }
+            })
+        });
+        c.bench_function("fast mod loop u32", |b| {
+            b.iter(|| {
+                for (dst, src) in dst.iter_mut().zip(black_box(&src)) {
+                    *dst = src.fast_mod(precomputed, d);
+                }
+            })
+        });
+        c.bench_function("fast count_multiples u32", |b| {
+            b.iter(|| black_box(u32::count_multiples(black_box(&src), precomputed)))
+        });
+        c.bench_function("slow count_multiples u32", |b| {
+            b.iter(|| black_box(black_box(&src).iter().filter(|&&n| n % d == 0).count()))
+        });
+    }
+    {
+        let d: u64 = black_box(7);
+        let precomputed = d.precompute_div();
+        let src: Vec<u64> = (0..1 << 16)
+            .map(|i: u64| i.wrapping_mul(11400714819323198485))
+            .collect();
+        let mut dst = vec![0; src.len()];
+
+        c.bench_function("fast div slice u64", |b| {
+            b.iter(|| u64::fast_div_slice_into(black_box(&src), &mut dst, precomputed))
+        });
+        c.bench_function("slow div slice u64", |b| {
+            b.iter(|| {
+                for (dst, src) in dst.iter_mut().zip(black_box(&src)) {
+                    *dst = src / d;
+                }
+            })
+        });
+        c.bench_function("fast div loop u64", |b| {
+            b.iter(|| {
+                for (dst, src) in dst.iter_mut().zip(black_box(&src)) {
+                    *dst = src.fast_div(precomputed);
+                }
+            })
+        });
+        c.bench_function("fast mod loop u64", |b| {
+            b.iter(|| {
+                for (dst, src) in dst.iter_mut().zip(black_box(&src)) {
+                    *dst = src.fast_mod(precomputed, d);
+                }
+            })
+        });
+        c.bench_function("fast count_multiples u64", |b| {
+            b.iter(|| black_box(u64::count_multiples(black_box(&src), precomputed)))
+        });
+        c.bench_function("slow count_multiples u64", |b| {
+            b.iter(|| black_box(black_box(&src).iter().filter(|&&n| n % d == 0).count()))
+        });
     }
 }
 
-criterion_group!(benches, criterion_benchmark);
+criterion_group!(benches, criterion_benchmark, slice_benchmark);
 criterion_main!(benches);
@@ -17,13 +17,67 @@
 //! assert_eq!(n1 % d, n1.fast_mod(m, d));
 //! assert_eq!(n2 % d, n2.fast_mod(m, d));
 //!
-//! assert_eq!(n1 % d == 0, n1.is_multiple_of(m));
-//! assert_eq!(n2 % d == 0, n2.is_multiple_of(m));
+//! assert_eq!(n1 % d == 0, FastDiv::is_multiple_of(n1, m));
+//! assert_eq!(n2 % d == 0, FastDiv::is_multiple_of(n2, m));
 //! ```
+//!
+//! The precomputed division factors can also be built and used in const contexts, e.g. for
+//! static lookup tables:
+//! ```
+//! use fastdiv::PrecomputedDivU32;
+//!
+//! static DIVISORS: [PrecomputedDivU32; 3] = [
+//!     PrecomputedDivU32::new(3),
+//!     PrecomputedDivU32::new(5),
+//!     PrecomputedDivU32::new(7),
+//! ];
+//! const QUOTIENT: u32 = PrecomputedDivU32::new(5).div(17);
+//!
+//! assert_eq!(QUOTIENT, 3);
+//! assert_eq!(DIVISORS[2].rem(17, 7), 3);
+//! assert!(DIVISORS[0].is_multiple_of(9));
+//! ```
+//!
+//! With the `portable_simd` feature (nightly only), `core::simd` vectors of `u32` and `u64` can
+//! be divided lane-wise, using `PrecomputedDivSimdU32` and `PrecomputedDivSimdU64`, or by a single
+//! divisor with the `*_simd` methods of `PrecomputedDivU32` and `PrecomputedDivU64`.
+//!
+//! With the `avx512` feature (Rust 1.89 or later), `u32` slice operations use AVX-512 kernels on
+//! x86_64 CPUs that support them.
+
+#![cfg_attr(feature = "portable_simd", feature(portable_simd))]
 
+use core::marker::PhantomData;
+use core::num::{
+    NonZeroI32, NonZeroI64, NonZeroIsize, NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64,
+    NonZeroU8, NonZeroUsize,
+};
+
+#[cfg(feature = "num-traits")]
+mod num;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "portable_simd")]
+mod simd;
+#[cfg(target_arch = "x86_64")]
+mod x86;
+
+#[cfg(feature = "num-traits")]
+pub use num::FastDivPrimInt;
+#[cfg(feature = "portable_simd")]
+pub use simd::{PrecomputedDivSimdU32, PrecomputedDivSimdU64};
+
+#[inline]
+const fn mul32_u8(lowbits: u16, d: u8) -> u16 {
+    ((lowbits as u32 * d as u32) >> 16) as u16
+}
+#[inline]
+const fn mul64_u16(lowbits: u32, d: u16) -> u32 {
+    ((lowbits as u64 * d as u64) >> 32) as u32
+}
 #[inline]
 const fn mul128_u32(lowbits: u64, d: u32) -> u64 {
-    (lowbits as u128 * d as u128 >> 64) as u64
+    ((lowbits as u128 * d as u128) >> 64) as u64
 }
 #[inline]
 const fn mul128_u64(lowbits: u128, d: u64) -> u64 {
@@ -33,50 +87,302 @@ const fn mul128_u64(lowbits: u128, d: u64) -> u64 {
     let both_halves = bottom_half + top_half;
     (both_halves >> 64) as u64
 }
+// full 128x128 -> 256 multiplication, returned as (low, high)
+#[inline]
+const fn widening_mul_u128(a: u128, b: u128) -> (u128, u128) {
+    let (a_lo, a_hi) = (a & 0xFFFFFFFFFFFFFFFF, a >> 64);
+    let (b_lo, b_hi) = (b & 0xFFFFFFFFFFFFFFFF, b >> 64);
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+    let cross = (lo_lo >> 64) + (hi_lo & 0xFFFFFFFFFFFFFFFF) + lo_hi;
+    let low = (cross << 64) | (lo_lo & 0xFFFFFFFFFFFFFFFF);
+    let high = hi_hi + (hi_lo >> 64) + (cross >> 64);
+    (low, high)
+}
+// 256-bit values are stored as [low, high], computes lowbits * a + a
+#[inline]
+const fn wrapping_mul_add_u256(lowbits: [u128; 2], a: u128) -> [u128; 2] {
+    let (lo, carry) = widening_mul_u128(lowbits[0], a);
+    let (lo, carry_add) = lo.overflowing_add(a);
+    let hi = carry
+        .wrapping_add(carry_add as u128)
+        .wrapping_add(lowbits[1].wrapping_mul(a));
+    [lo, hi]
+}
+#[inline]
+const fn mul256_u128(lowbits: [u128; 2], d: u128) -> u128 {
+    let (_, bottom_half) = widening_mul_u128(lowbits[0], d);
+    let (top_lo, top_hi) = widening_mul_u128(lowbits[1], d);
+    let (_, carry) = bottom_half.overflowing_add(top_lo);
+    top_hi + carry as u128
+}
+// divides the 256-bit value [lo, hi] by d, for hi < d. this is Knuth's algorithm D with 64-bit
+// digits: each quotient digit is estimated from the top digits and corrected at most twice
+#[inline]
+const fn div256_u128(lo: u128, hi: u128, d: u128) -> u128 {
+    const B: u128 = 1 << 64;
+    let s = d.leading_zeros();
+    let v = d << s;
+    let (vn1, vn0) = (v >> 64, v & (B - 1));
+    let un32 = if s == 0 {
+        hi
+    } else {
+        (hi << s) | (lo >> (128 - s))
+    };
+    let un10 = lo << s;
+    let (un1, un0) = (un10 >> 64, un10 & (B - 1));
+
+    let mut q1 = un32 / vn1;
+    let mut rhat = un32 - q1 * vn1;
+    while q1 >= B || q1 * vn0 > (rhat << 64) | un1 {
+        q1 -= 1;
+        rhat += vn1;
+        if rhat >= B {
+            break;
+        }
+    }
+    let un21 = (un32 << 64 | un1).wrapping_sub(q1.wrapping_mul(v));
+
+    let mut q0 = un21 / vn1;
+    let mut rhat = un21 - q0 * vn1;
+    while q0 >= B || q0 * vn0 > (rhat << 64) | un0 {
+        q0 -= 1;
+        rhat += vn1;
+        if rhat >= B {
+            break;
+        }
+    }
+    q1 << 64 | q0
+}
+
+// the precomputed factors store `m - 1` rather than Lemire's `m`, so that `d == 1` (where `m`
+// would overflow) is representable. the missing `+ 1` is folded into the products as `+ a`.
+// since the factors have twice the bits of the divisor, `d` can be recovered as `MAX / m`.
+
+#[inline]
+const fn compute_m_u8(d: u8) -> u16 {
+    0xFFFF / d as u16
+}
+#[inline]
+const fn compute_d_u8(m: u16) -> u8 {
+    (0xFFFF / m) as u8
+}
+#[inline]
+const fn fastmod_u8(a: u8, m: u16, d: u8) -> u8 {
+    let lowbits = m.wrapping_mul(a as u16).wrapping_add(a as u16);
+    mul32_u8(lowbits, d) as u8
+}
+#[inline]
+const fn fastdiv_u8(a: u8, m: u16) -> u8 {
+    ((m as u32 * a as u32 + a as u32) >> 16) as u8
+}
+#[inline]
+const fn is_divisible_u8(n: u8, m: u16) -> bool {
+    (n as u16).wrapping_mul(m).wrapping_add(n as u16) <= m
+}
+
+#[inline]
+const fn compute_m_u16(d: u16) -> u32 {
+    0xFFFFFFFF / d as u32
+}
+#[inline]
+const fn compute_d_u16(m: u32) -> u16 {
+    (0xFFFFFFFF / m) as u16
+}
+#[inline]
+const fn fastmod_u16(a: u16, m: u32, d: u16) -> u16 {
+    let lowbits = m.wrapping_mul(a as u32).wrapping_add(a as u32);
+    mul64_u16(lowbits, d) as u16
+}
+#[inline]
+const fn fastdiv_u16(a: u16, m: u32) -> u16 {
+    ((m as u64 * a as u64 + a as u64) >> 32) as u16
+}
+#[inline]
+const fn is_divisible_u16(n: u16, m: u32) -> bool {
+    (n as u32).wrapping_mul(m).wrapping_add(n as u32) <= m
+}
 
 #[inline]
 const fn compute_m_u32(d: u32) -> u64 {
-    (0xFFFFFFFFFFFFFFFF / d as u64) + 1
+    0xFFFFFFFFFFFFFFFF / d as u64
+}
+#[inline]
+const fn compute_d_u32(m: u64) -> u32 {
+    (0xFFFFFFFFFFFFFFFF / m) as u32
 }
 #[inline]
 const fn fastmod_u32(a: u32, m: u64, d: u32) -> u32 {
-    let lowbits = m.wrapping_mul(a as u64);
+    let lowbits = m.wrapping_mul(a as u64).wrapping_add(a as u64);
     mul128_u32(lowbits, d) as u32
 }
-// for d > 1
 #[inline]
 const fn fastdiv_u32(a: u32, m: u64) -> u32 {
-    mul128_u32(m, a) as u32
+    ((m as u128 * a as u128 + a as u128) >> 64) as u32
 }
 #[inline]
 const fn is_divisible_u32(n: u32, m: u64) -> bool {
-    (n as u64).wrapping_mul(m) <= m - 1
+    (n as u64).wrapping_mul(m).wrapping_add(n as u64) <= m
 }
 
 #[inline]
 const fn compute_m_u64(d: u64) -> u128 {
-    (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF / d as u128) + 1
+    0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF / d as u128
+}
+#[inline]
+const fn compute_d_u64(m: u128) -> u64 {
+    (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF / m) as u64
 }
 #[inline]
 const fn fastmod_u64(a: u64, m: u128, d: u64) -> u64 {
-    let lowbits = m.wrapping_mul(a as u128);
+    let lowbits = m.wrapping_mul(a as u128).wrapping_add(a as u128);
     mul128_u64(lowbits, d)
 }
-// for d > 1
 #[inline]
 const fn fastdiv_u64(a: u64, m: u128) -> u64 {
-    mul128_u64(m, a)
+    let mut bottom_half = (m & 0xFFFFFFFFFFFFFFFF) * a as u128 + a as u128;
+    bottom_half >>= 64;
+    let top_half = (m >> 64) * a as u128;
+    let both_halves = bottom_half + top_half;
+    (both_halves >> 64) as u64
 }
 #[inline]
 const fn is_divisible_u64(n: u64, m: u128) -> bool {
-    (n as u128).wrapping_mul(m) <= m - 1
+    (n as u128).wrapping_mul(m).wrapping_add(n as u128) <= m
+}
+
+// variant of `fastdiv_u32` for batched operations, split into 32x32 -> 64 bit products, which
+// vectorize well
+#[inline]
+const fn fastdiv_u32_batch(a: u32, m: u64) -> u32 {
+    let (m_lo, m_hi) = (m & 0xFFFFFFFF, m >> 32);
+    let a = a as u64;
+    let bottom_half = (m_lo * a + a) >> 32;
+    ((m_hi * a + bottom_half) >> 32) as u32
+}
+
+#[inline]
+const fn compute_m_u128(d: u128) -> [u128; 2] {
+    let hi = u128::MAX / d;
+    let lo = div256_u128(u128::MAX, u128::MAX % d, d);
+    [lo, hi]
+}
+#[inline]
+const fn fastmod_u128(a: u128, m: [u128; 2], d: u128) -> u128 {
+    let lowbits = wrapping_mul_add_u256(m, a);
+    mul256_u128(lowbits, d)
+}
+#[inline]
+const fn fastdiv_u128(a: u128, m: [u128; 2]) -> u128 {
+    let (bottom_lo, bottom_hi) = widening_mul_u128(m[0], a);
+    let (_, carry) = bottom_lo.overflowing_add(a);
+    let bottom_half = bottom_hi + carry as u128;
+    let (top_lo, top_hi) = widening_mul_u128(m[1], a);
+    let (_, carry) = bottom_half.overflowing_add(top_lo);
+    top_hi + carry as u128
+}
+// `d` is `MAX / m` as a 256-bit division. dividing by the top 128 bits of `m` instead gives an
+// estimate that is at most 2 too large, which is then corrected by checking `d * m <= MAX`
+#[inline]
+const fn compute_d_u128(m: [u128; 2]) -> u128 {
+    let s = m[1].leading_zeros();
+    let top = if s == 0 {
+        m[1]
+    } else {
+        (m[1] << s) | (m[0] >> (128 - s))
+    };
+    let mut d = div256_u128(u128::MAX, (1 << s) - 1, top);
+    loop {
+        let (_, carry) = widening_mul_u128(m[0], d);
+        let (top_lo, top_hi) = widening_mul_u128(m[1], d);
+        let (_, overflow) = carry.overflowing_add(top_lo);
+        if top_hi == 0 && !overflow {
+            return d;
+        }
+        d -= 1;
+    }
+}
+#[inline]
+const fn is_divisible_u128(n: u128, m: [u128; 2]) -> bool {
+    let lowbits = wrapping_mul_add_u256(m, n);
+    lowbits[1] < m[1] || (lowbits[1] == m[1] && lowbits[0] <= m[0])
+}
+
+// sign-magnitude helpers for signed division, truncating towards zero
+#[inline]
+const fn fastdiv_i32(a: i32, m: u64, negative: bool) -> i32 {
+    let sign = (a >> 31) ^ -(negative as i32);
+    let q = fastdiv_u32(a.unsigned_abs(), m) as i32;
+    (q ^ sign).wrapping_sub(sign)
+}
+#[inline]
+const fn fastmod_i32(a: i32, m: u64, d: i32) -> i32 {
+    let sign = a >> 31;
+    let r = fastmod_u32(a.unsigned_abs(), m, d.unsigned_abs()) as i32;
+    (r ^ sign).wrapping_sub(sign)
 }
+#[inline]
+const fn is_divisible_i32(n: i32, m: u64) -> bool {
+    is_divisible_u32(n.unsigned_abs(), m)
+}
+
+#[inline]
+const fn fastdiv_i64(a: i64, m: u128, negative: bool) -> i64 {
+    let sign = (a >> 63) ^ -(negative as i64);
+    let q = fastdiv_u64(a.unsigned_abs(), m) as i64;
+    (q ^ sign).wrapping_sub(sign)
+}
+#[inline]
+const fn fastmod_i64(a: i64, m: u128, d: i64) -> i64 {
+    let sign = a >> 63;
+    let r = fastmod_u64(a.unsigned_abs(), m, d.unsigned_abs()) as i64;
+    (r ^ sign).wrapping_sub(sign)
+}
+#[inline]
+const fn is_divisible_i64(n: i64, m: u128) -> bool {
+    is_divisible_u64(n.unsigned_abs(), m)
+}
+
+/// Error returned when a division factor cannot be precomputed from a divisor.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum DivisorError {
+    /// The divisor is zero.
+    Zero,
+    /// The divisor is nonzero, but cannot be represented by the precomputed division factor.
+    Unsupported,
+}
+
+impl core::fmt::Display for DivisorError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DivisorError::Zero => f.write_str("divisor is zero"),
+            DivisorError::Unsupported => f.write_str("divisor is not supported"),
+        }
+    }
+}
+
+impl std::error::Error for DivisorError {}
 
 /// Allows precomputing the division factor for fast division, modulo, and divisibility checks.
 pub trait FastDiv: Copy {
     type PrecomputedDiv: Copy;
     /// Precompute the division factor from the divisor `self`.
-    fn precompute_div(self) -> Self::PrecomputedDiv;
+    ///
+    /// # Panics
+    /// Panics if `self` is not a valid divisor, see [`FastDiv::try_precompute_div`].
+    #[inline]
+    fn precompute_div(self) -> Self::PrecomputedDiv {
+        match self.try_precompute_div() {
+            Ok(precomputed) => precomputed,
+            Err(err) => panic!("{}", err),
+        }
+    }
+    /// Precompute the division factor from the divisor `self`, or return an error if `self` is
+    /// zero.
+    fn try_precompute_div(self) -> Result<Self::PrecomputedDiv, DivisorError>;
     /// Divide by the divisor, given the precomputed division factor.
     fn fast_div(self, precomputed: Self::PrecomputedDiv) -> Self;
     /// Compute the remainder of the division of `self` by the divisor, given the precomputed division factor and the divisor `d`.
@@ -85,98 +391,2583 @@ pub trait FastDiv: Copy {
     fn fast_mod(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self;
     /// Check if `self` is a multiple of the divisor, given the precomputed division factor.
     fn is_multiple_of(self, precomputed: Self::PrecomputedDiv) -> bool;
+    /// Compute both the quotient and the remainder of the division of `self` by the divisor,
+    /// given the precomputed division factor and the divisor `d`. This is faster than calling
+    /// `fast_div` and `fast_mod` separately.
+    /// If the precomputed division factor does not come from the same provided divisor, the
+    /// result is unspecified.
+    #[inline]
+    fn fast_div_rem(self, precomputed: Self::PrecomputedDiv, d: Self) -> (Self, Self) {
+        (self.fast_div(precomputed), self.fast_mod(precomputed, d))
+    }
+    /// Divide by the divisor, rounding the quotient towards positive infinity, given the
+    /// precomputed division factor. Unlike `(n + d - 1) / d`, this never overflows.
+    fn fast_div_ceil(self, precomputed: Self::PrecomputedDiv) -> Self;
+    /// Divide by the divisor, rounding the quotient to the nearest integer with ties rounded away
+    /// from zero (i.e. half-up for unsigned integers), given the precomputed division factor and
+    /// the divisor `d`. This never overflows.
+    /// If the precomputed division factor does not come from the same provided divisor, the
+    /// result is unspecified.
+    fn fast_div_round(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self;
+    /// Divide by the divisor, rounding the quotient to the nearest integer with ties rounded to
+    /// the nearest even integer, given the precomputed division factor and the divisor `d`. This
+    /// never overflows.
+    /// If the precomputed division factor does not come from the same provided divisor, the
+    /// result is unspecified.
+    fn fast_div_round_half_even(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self;
+
+    /// Compute the quotient of the Euclidean division of `self` by the divisor, given the
+    /// precomputed division factor. For unsigned integers, this is the same as `fast_div`.
+    #[inline]
+    fn fast_div_euclid(self, precomputed: Self::PrecomputedDiv) -> Self {
+        self.fast_div(precomputed)
+    }
+    /// Compute the least nonnegative remainder of `self` modulo the divisor, given the
+    /// precomputed division factor and the divisor `d`. For unsigned integers, this is the same
+    /// as `fast_mod`.
+    #[inline]
+    fn fast_rem_euclid(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+        self.fast_mod(precomputed, d)
+    }
+    /// Divide by the divisor, rounding the quotient towards negative infinity, given the
+    /// precomputed division factor. For unsigned integers, this is the same as `fast_div`.
+    #[inline]
+    fn fast_div_floor(self, precomputed: Self::PrecomputedDiv) -> Self {
+        self.fast_div(precomputed)
+    }
+    /// Compute the remainder of the floored division of `self` by the divisor, which has the
+    /// same sign as the divisor, given the precomputed division factor and the divisor `d`. For
+    /// unsigned integers, this is the same as `fast_mod`.
+    #[inline]
+    fn fast_mod_floor(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+        self.fast_mod(precomputed, d)
+    }
+
+    /// Divide every element of `values` by the divisor in place, given the precomputed division
+    /// factor.
+    #[inline]
+    fn fast_div_slice(values: &mut [Self], precomputed: Self::PrecomputedDiv) {
+        for value in values {
+            *value = value.fast_div(precomputed);
+        }
+    }
+    /// Replace every element of `values` by its remainder modulo the divisor, given the
+    /// precomputed division factor and the divisor `d`.
+    /// If the precomputed division factor does not come from the same provided divisor, the
+    /// result is unspecified.
+    #[inline]
+    fn fast_mod_slice(values: &mut [Self], precomputed: Self::PrecomputedDiv, d: Self) {
+        for value in values {
+            *value = value.fast_mod(precomputed, d);
+        }
+    }
+    /// Divide every element of `src` by the divisor and write the quotients to `dst`, given the
+    /// precomputed division factor.
+    ///
+    /// # Panics
+    /// Panics if `src` and `dst` have different lengths.
+    #[inline]
+    fn fast_div_slice_into(src: &[Self], dst: &mut [Self], precomputed: Self::PrecomputedDiv) {
+        assert_eq!(src.len(), dst.len());
+        for (dst, src) in dst.iter_mut().zip(src) {
+            *dst = src.fast_div(precomputed);
+        }
+    }
+    /// Count the elements of `values` that are multiples of the divisor, given the precomputed
+    /// division factor.
+    #[inline]
+    fn count_multiples(values: &[Self], precomputed: Self::PrecomputedDiv) -> usize {
+        values
+            .iter()
+            .filter(|value| value.is_multiple_of(precomputed))
+            .count()
+    }
+}
+
+/// A precomputed divisor, for code that is generic over the value it divides by.
+///
+/// Unlike [`FastDiv`], the operations are on the divisor side, so the remainder does not need
+/// the divisor to be passed back in.
+///
+/// This is implemented by the precomputed division factors, [`Divisor`] and [`ConstDiv`].
+///
+/// The precomputed factors only store the factor, so their `divisor` recovers the divisor from it
+/// with a division, and `rem` computes `n - div(n) * divisor()`. That makes `rem` slower than the
+/// native `%` operator, and much slower for `u128`. When remainders are needed in a hot loop, use
+/// a [`Divisor`], which keeps the divisor next to the factor.
+///
+/// # Example
+/// ```
+/// use fastdiv::{ConstDiv, Divisor, PrecomputedDivU32, PrecomputedDivisor};
+///
+/// fn digits<D: PrecomputedDivisor<Int = u32>>(mut n: u32, base: &D) -> Vec<u32> {
+///     let mut digits = vec![base.rem(n)];
+///     while base.div(n) > 0 {
+///         n = base.div(n);
+///         digits.push(base.rem(n));
+///     }
+///     digits
+/// }
+///
+/// assert_eq!(digits(1234, &Divisor::new(10)), [4, 3, 2, 1]);
+/// assert_eq!(digits(1234, &PrecomputedDivU32::new(10)), [4, 3, 2, 1]);
+/// assert_eq!(digits(10, &ConstDiv::<2, u32>::new()), [0, 1, 0, 1]);
+/// ```
+pub trait PrecomputedDivisor {
+    /// The integer type of the divisor.
+    type Int: Copy;
+
+    /// Divide `n` by the divisor.
+    fn div(&self, n: Self::Int) -> Self::Int;
+    /// Compute the remainder of the division of `n` by the divisor.
+    fn rem(&self, n: Self::Int) -> Self::Int;
+    /// Check if `n` is a multiple of the divisor.
+    fn is_multiple(&self, n: Self::Int) -> bool;
+    /// Returns the divisor.
+    fn divisor(&self) -> Self::Int;
 }
 
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub struct PrecomputedDivU8 {
+    m: u16,
+}
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub struct PrecomputedDivU16 {
+    m: u32,
+}
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
 pub struct PrecomputedDivU32 {
     m: u64,
+    pow2_shift: u32,
 }
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
 pub struct PrecomputedDivU64 {
     m: u128,
+    pow2_shift: u32,
+}
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub struct PrecomputedDivU128 {
+    m: [u128; 2],
 }
 
-impl FastDiv for u32 {
-    type PrecomputedDiv = PrecomputedDivU32;
-
-    #[inline]
-    fn precompute_div(self) -> Self::PrecomputedDiv {
-        assert!(self > 1);
-        Self::PrecomputedDiv {
-            m: compute_m_u32(self),
-        }
-    }
+/// Precomputed division factor for a signed `i32` divisor.
+///
+/// Quotients and remainders are truncated towards zero, matching `/` and `%`. Dividing
+/// `i32::MIN` by `-1` wraps around to `i32::MIN` with a remainder of `0`, like
+/// [`i32::wrapping_div`] and [`i32::wrapping_rem`], instead of panicking.
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub struct PrecomputedDivI32 {
+    m: u64,
+    negative: bool,
+}
+/// Precomputed division factor for a signed `i64` divisor.
+///
+/// Quotients and remainders are truncated towards zero, matching `/` and `%`. Dividing
+/// `i64::MIN` by `-1` wraps around to `i64::MIN` with a remainder of `0`, like
+/// [`i64::wrapping_div`] and [`i64::wrapping_rem`], instead of panicking.
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub struct PrecomputedDivI64 {
+    m: u128,
+    negative: bool,
+}
 
-    #[inline]
-    fn fast_div(self, precomputed: Self::PrecomputedDiv) -> Self {
-        fastdiv_u32(self, precomputed.m)
-    }
+#[cfg(target_pointer_width = "32")]
+type UsizeRepr = u32;
+#[cfg(target_pointer_width = "64")]
+type UsizeRepr = u64;
+#[cfg(target_pointer_width = "32")]
+type IsizeRepr = i32;
+#[cfg(target_pointer_width = "64")]
+type IsizeRepr = i64;
+// spelled out rather than projected from the repr types, since trait impls on a projection are
+// rejected by the orphan check of older compilers
+#[cfg(target_pointer_width = "32")]
+type UsizePrecomputedRepr = PrecomputedDivU32;
+#[cfg(target_pointer_width = "64")]
+type UsizePrecomputedRepr = PrecomputedDivU64;
+#[cfg(target_pointer_width = "32")]
+type IsizePrecomputedRepr = PrecomputedDivI32;
+#[cfg(target_pointer_width = "64")]
+type IsizePrecomputedRepr = PrecomputedDivI64;
 
-    #[inline]
-    fn fast_mod(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
-        fastmod_u32(self, precomputed.m, d)
-    }
+/// Precomputed division factor for a `usize` divisor, using the implementation matching the
+/// pointer width of the target.
+pub type PrecomputedDivUsize = UsizePrecomputedRepr;
+/// Precomputed division factor for an `isize` divisor, using the implementation matching the
+/// pointer width of the target.
+pub type PrecomputedDivIsize = IsizePrecomputedRepr;
 
+// Explicit SIMD kernels for the slice operations, used where auto-vectorization falls short.
+trait SimdKernels: FastDiv {
+    /// Divides a prefix of `src` into `dst`, or computes the remainders if `d` is given, and
+    /// returns the length of the prefix. The rest is left to the scalar loop.
+    ///
+    /// # Safety
+    /// `src` must be valid for `len` reads and `dst` for `len` writes. They may be equal, but must
+    /// not otherwise overlap.
     #[inline]
-    fn is_multiple_of(self, precomputed: Self::PrecomputedDiv) -> bool {
-        is_divisible_u32(self, precomputed.m)
+    unsafe fn simd_div(
+        _src: *const Self,
+        _dst: *mut Self,
+        _len: usize,
+        _precomputed: Self::PrecomputedDiv,
+        _d: Option<Self>,
+    ) -> usize {
+        0
     }
 }
 
-impl FastDiv for u64 {
-    type PrecomputedDiv = PrecomputedDivU64;
+impl SimdKernels for u8 {}
+impl SimdKernels for u16 {}
+#[cfg(not(target_arch = "x86_64"))]
+impl SimdKernels for u32 {}
+#[cfg(not(target_arch = "x86_64"))]
+impl SimdKernels for u64 {}
+impl SimdKernels for u128 {}
 
-    #[inline]
-    fn precompute_div(self) -> Self::PrecomputedDiv {
-        assert!(self > 1);
-        Self::PrecomputedDiv {
-            m: compute_m_u64(self),
+// `$fastdiv_batch` is used for the scalar tail of the slice operations, after `simd_div`. it must
+// be branchless. `fastdiv_u32_batch` vectorizes on its own, while the u128 products of
+// `fastdiv_u64` do not, so u64 slices vectorize through the AVX2 kernel in `x86.rs` when available
+// and otherwise run as scalar loops.
+// `$pow2_shift`, if given, names a field holding `log2(d)` for power of two divisors and `BITS`
+// otherwise. it is set when the factor is precomputed, and division, modulo and divisibility then
+// reduce to a shift, a mask and a comparison. the general helpers stay branchless, and for the
+// types without the field `pow2_shift()` is a constant, so the check folds away.
+macro_rules! impl_fast_div_unsigned {
+    ($ty: ty, $nonzero: ty, $precomputed: ty, $compute_m: ident, $compute_d: ident, $fastdiv: ident, $fastmod: ident, $is_divisible: ident, $fastdiv_batch: ident $(, $pow2_shift: ident)?) => {
+        impl $precomputed {
+            /// Precompute the division factor for the divisor `d`.
+            ///
+            /// # Panics
+            /// Panics if `d` is zero.
+            #[inline]
+            pub const fn new(d: $ty) -> Self {
+                assert!(d != 0, "divisor is zero");
+                Self::new_unchecked(d)
+            }
+
+            /// Precompute the division factor for the divisor `d`, or return an error if `d` is
+            /// zero.
+            #[inline]
+            pub const fn try_new(d: $ty) -> Result<Self, DivisorError> {
+                if d == 0 {
+                    return Err(DivisorError::Zero);
+                }
+                Ok(Self::new_unchecked(d))
+            }
+
+            #[inline]
+            const fn new_unchecked(d: $ty) -> Self {
+                Self {
+                    m: $compute_m(d),
+                    $($pow2_shift: if d.is_power_of_two() {
+                        d.trailing_zeros()
+                    } else {
+                        <$ty>::BITS
+                    },)?
+                }
+            }
+
+            #[inline]
+            #[allow(unreachable_code)]
+            const fn pow2_shift(&self) -> u32 {
+                $(return self.$pow2_shift;)?
+                <$ty>::BITS
+            }
+
+            /// Recover the divisor from the precomputed division factor.
+            #[inline]
+            pub const fn divisor(&self) -> $ty {
+                $compute_d(self.m)
+            }
+
+            /// Divide `n` by the divisor.
+            #[inline]
+            pub const fn div(&self, n: $ty) -> $ty {
+                let shift = self.pow2_shift();
+                if shift < <$ty>::BITS {
+                    return n >> shift;
+                }
+                $fastdiv(n, self.m)
+            }
+
+            /// Compute the remainder of the division of `n` by the divisor `d`.
+            /// If `self` does not come from the same provided divisor, the result is unspecified.
+            #[inline]
+            pub const fn rem(&self, n: $ty, d: $ty) -> $ty {
+                let shift = self.pow2_shift();
+                if shift < <$ty>::BITS {
+                    return n & !(<$ty>::MAX << shift);
+                }
+                $fastmod(n, self.m, d)
+            }
+
+            /// Compute both the quotient and the remainder of the division of `n` by the divisor
+            /// `d`.
+            /// If `self` does not come from the same provided divisor, the result is unspecified.
+            #[inline]
+            pub const fn div_rem(&self, n: $ty, d: $ty) -> ($ty, $ty) {
+                let q = self.div(n);
+                (q, n.wrapping_sub(q.wrapping_mul(d)))
+            }
+
+            /// Check if `n` is a multiple of the divisor.
+            #[inline]
+            pub const fn is_multiple_of(&self, n: $ty) -> bool {
+                let shift = self.pow2_shift();
+                if shift < <$ty>::BITS {
+                    return n & !(<$ty>::MAX << shift) == 0;
+                }
+                $is_divisible(n, self.m)
+            }
+
+            /// Divide `n` by the divisor, rounding the quotient towards negative infinity. For
+            /// unsigned integers, this is the same as `div`.
+            #[inline]
+            pub const fn div_floor(&self, n: $ty) -> $ty {
+                self.div(n)
+            }
+
+            /// Compute the remainder of the floored division of `n` by the divisor `d`. For
+            /// unsigned integers, this is the same as `rem`.
+            /// If `self` does not come from the same provided divisor, the result is unspecified.
+            #[inline]
+            pub const fn mod_floor(&self, n: $ty, d: $ty) -> $ty {
+                self.rem(n, d)
+            }
         }
-    }
 
-    #[inline]
-    fn fast_div(self, precomputed: Self::PrecomputedDiv) -> Self {
-        fastdiv_u64(self, precomputed.m)
-    }
+        impl From<$nonzero> for $precomputed {
+            #[inline]
+            fn from(d: $nonzero) -> Self {
+                Self::new_unchecked(d.get())
+            }
+        }
 
-    #[inline]
-    fn fast_mod(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
-        fastmod_u64(self, precomputed.m, d)
-    }
+        impl FastDiv for $ty {
+            type PrecomputedDiv = $precomputed;
 
-    #[inline]
-    fn is_multiple_of(self, precomputed: Self::PrecomputedDiv) -> bool {
-        is_divisible_u64(self, precomputed.m)
-    }
+            #[inline]
+            fn try_precompute_div(self) -> Result<Self::PrecomputedDiv, DivisorError> {
+                <$precomputed>::try_new(self)
+            }
+
+            #[inline]
+            fn fast_div(self, precomputed: Self::PrecomputedDiv) -> Self {
+                precomputed.div(self)
+            }
+
+            #[inline]
+            fn fast_mod(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                precomputed.rem(self, d)
+            }
+
+            #[inline]
+            fn is_multiple_of(self, precomputed: Self::PrecomputedDiv) -> bool {
+                precomputed.is_multiple_of(self)
+            }
+
+            #[inline]
+            fn fast_div_rem(self, precomputed: Self::PrecomputedDiv, d: Self) -> (Self, Self) {
+                precomputed.div_rem(self, d)
+            }
+
+            #[inline]
+            fn fast_div_ceil(self, precomputed: Self::PrecomputedDiv) -> Self {
+                precomputed.div(self) + !precomputed.is_multiple_of(self) as Self
+            }
+
+            #[inline]
+            fn fast_div_round(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                let (q, r) = precomputed.div_rem(self, d);
+                // `2 * r >= d`, without overflowing
+                q + (r >= d - r) as Self
+            }
+
+            #[inline]
+            fn fast_div_round_half_even(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                let (q, r) = precomputed.div_rem(self, d);
+                q + (r > d - r || (r == d - r && q & 1 == 1)) as Self
+            }
+
+            #[inline]
+            fn fast_div_slice(values: &mut [Self], precomputed: Self::PrecomputedDiv) {
+                let shift = precomputed.pow2_shift();
+                if shift < <$ty>::BITS {
+                    values.iter_mut().for_each(|value| *value >>= shift);
+                    return;
+                }
+                let ptr = values.as_mut_ptr();
+                // SAFETY: `ptr` is valid for `values.len()` reads and writes
+                let done = unsafe { Self::simd_div(ptr, ptr, values.len(), precomputed, None) };
+                let m = precomputed.m;
+                for value in &mut values[done..] {
+                    *value = $fastdiv_batch(*value, m);
+                }
+            }
+
+            #[inline]
+            fn fast_mod_slice(values: &mut [Self], precomputed: Self::PrecomputedDiv, d: Self) {
+                let shift = precomputed.pow2_shift();
+                if shift < <$ty>::BITS {
+                    let mask = !(<$ty>::MAX << shift);
+                    values.iter_mut().for_each(|value| *value &= mask);
+                    return;
+                }
+                let ptr = values.as_mut_ptr();
+                // SAFETY: `ptr` is valid for `values.len()` reads and writes
+                let done = unsafe { Self::simd_div(ptr, ptr, values.len(), precomputed, Some(d)) };
+                let m = precomputed.m;
+                for value in &mut values[done..] {
+                    let q = $fastdiv_batch(*value, m);
+                    *value = value.wrapping_sub(q.wrapping_mul(d));
+                }
+            }
+
+            #[inline]
+            fn fast_div_slice_into(
+                src: &[Self],
+                dst: &mut [Self],
+                precomputed: Self::PrecomputedDiv,
+            ) {
+                assert_eq!(src.len(), dst.len());
+                let shift = precomputed.pow2_shift();
+                if shift < <$ty>::BITS {
+                    for (dst, src) in dst.iter_mut().zip(src) {
+                        *dst = src >> shift;
+                    }
+                    return;
+                }
+                // SAFETY: the slices have the same length, and cannot overlap since `dst` is
+                // borrowed mutably
+                let done = unsafe {
+                    Self::simd_div(src.as_ptr(), dst.as_mut_ptr(), src.len(), precomputed, None)
+                };
+                let m = precomputed.m;
+                for (dst, src) in dst[done..].iter_mut().zip(&src[done..]) {
+                    *dst = $fastdiv_batch(*src, m);
+                }
+            }
+
+            #[inline]
+            fn count_multiples(values: &[Self], precomputed: Self::PrecomputedDiv) -> usize {
+                let shift = precomputed.pow2_shift();
+                if shift < <$ty>::BITS {
+                    let mask = !(<$ty>::MAX << shift);
+                    return values.iter().map(|&value| (value & mask == 0) as usize).sum();
+                }
+                let m = precomputed.m;
+                values
+                    .iter()
+                    .map(|&value| $is_divisible(value, m) as usize)
+                    .sum()
+            }
+        }
+    };
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl_fast_div_unsigned!(
+    u8,
+    NonZeroU8,
+    PrecomputedDivU8,
+    compute_m_u8,
+    compute_d_u8,
+    fastdiv_u8,
+    fastmod_u8,
+    is_divisible_u8,
+    fastdiv_u8
+);
+impl_fast_div_unsigned!(
+    u16,
+    NonZeroU16,
+    PrecomputedDivU16,
+    compute_m_u16,
+    compute_d_u16,
+    fastdiv_u16,
+    fastmod_u16,
+    is_divisible_u16,
+    fastdiv_u16
+);
+impl_fast_div_unsigned!(
+    u32,
+    NonZeroU32,
+    PrecomputedDivU32,
+    compute_m_u32,
+    compute_d_u32,
+    fastdiv_u32,
+    fastmod_u32,
+    is_divisible_u32,
+    fastdiv_u32_batch,
+    pow2_shift
+);
+impl_fast_div_unsigned!(
+    u64,
+    NonZeroU64,
+    PrecomputedDivU64,
+    compute_m_u64,
+    compute_d_u64,
+    fastdiv_u64,
+    fastmod_u64,
+    is_divisible_u64,
+    fastdiv_u64,
+    pow2_shift
+);
+impl_fast_div_unsigned!(
+    u128,
+    NonZeroU128,
+    PrecomputedDivU128,
+    compute_m_u128,
+    compute_d_u128,
+    fastdiv_u128,
+    fastmod_u128,
+    is_divisible_u128,
+    fastdiv_u128
+);
 
-    #[test]
-    fn div_u32() {
-        let n: u32 = 1000;
-        for j in 2..n {
-            let p = j.precompute_div();
-            for i in 0..n {
-                assert_eq!(i.fast_mod(p, j), i % j);
-                assert_eq!(i.fast_div(p), i / j);
-                assert_eq!(i.is_multiple_of(p), i % j == 0);
+macro_rules! impl_fast_div_signed {
+    ($ty: ty, $nonzero: ty, $precomputed: ty, $compute_m: ident, $compute_d: ident, $fastdiv: ident, $fastmod: ident, $is_divisible: ident) => {
+        impl $precomputed {
+            /// Precompute the division factor for the divisor `d`.
+            ///
+            /// # Panics
+            /// Panics if `d` is zero.
+            #[inline]
+            pub const fn new(d: $ty) -> Self {
+                assert!(d != 0, "divisor is zero");
+                Self {
+                    m: $compute_m(d.unsigned_abs()),
+                    negative: d < 0,
+                }
+            }
+
+            /// Precompute the division factor for the divisor `d`, or return an error if `d` is
+            /// zero.
+            #[inline]
+            pub const fn try_new(d: $ty) -> Result<Self, DivisorError> {
+                if d == 0 {
+                    return Err(DivisorError::Zero);
+                }
+                Ok(Self {
+                    m: $compute_m(d.unsigned_abs()),
+                    negative: d < 0,
+                })
+            }
+
+            /// Recover the divisor from the precomputed division factor.
+            #[inline]
+            pub const fn divisor(&self) -> $ty {
+                // the magnitude of `MIN` wraps back to `MIN`
+                let d = $compute_d(self.m) as $ty;
+                if self.negative {
+                    d.wrapping_neg()
+                } else {
+                    d
+                }
+            }
+
+            /// Divide `n` by the divisor, truncating towards zero.
+            #[inline]
+            pub const fn div(&self, n: $ty) -> $ty {
+                $fastdiv(n, self.m, self.negative)
+            }
+
+            /// Compute the remainder of the division of `n` by the divisor `d`, which has the
+            /// same sign as `n`.
+            /// If `self` does not come from the same provided divisor, the result is unspecified.
+            #[inline]
+            pub const fn rem(&self, n: $ty, d: $ty) -> $ty {
+                $fastmod(n, self.m, d)
+            }
+
+            /// Compute both the quotient and the remainder of the division of `n` by the divisor
+            /// `d`.
+            /// If `self` does not come from the same provided divisor, the result is unspecified.
+            #[inline]
+            pub const fn div_rem(&self, n: $ty, d: $ty) -> ($ty, $ty) {
+                let q = self.div(n);
+                (q, n.wrapping_sub(q.wrapping_mul(d)))
+            }
+
+            /// Check if `n` is a multiple of the divisor.
+            #[inline]
+            pub const fn is_multiple_of(&self, n: $ty) -> bool {
+                $is_divisible(n, self.m)
+            }
+
+            /// Divide `n` by the divisor, rounding the quotient towards negative infinity.
+            #[inline]
+            pub const fn div_floor(&self, n: $ty) -> $ty {
+                let q = self.div(n);
+                let inexact = !self.is_multiple_of(n);
+                q - (inexact && (n < 0) != self.negative) as $ty
+            }
+
+            /// Compute the remainder of the floored division of `n` by the divisor `d`, which
+            /// has the same sign as `d`.
+            /// If `self` does not come from the same provided divisor, the result is unspecified.
+            #[inline]
+            pub const fn mod_floor(&self, n: $ty, d: $ty) -> $ty {
+                let r = self.rem(n, d);
+                if r != 0 && (r < 0) != (d < 0) {
+                    r + d
+                } else {
+                    r
+                }
             }
         }
-    }
 
-    #[test]
-    fn div_u64() {
-        let n: u64 = 1000;
-        for j in 2..n {
-            let p = j.precompute_div();
-            for i in 0..n {
-                assert_eq!(i.fast_mod(p, j), i % j);
-                assert_eq!(i.fast_div(p), i / j);
-                assert_eq!(i.is_multiple_of(p), i % j == 0);
+        impl From<$nonzero> for $precomputed {
+            #[inline]
+            fn from(d: $nonzero) -> Self {
+                Self {
+                    m: $compute_m(d.get().unsigned_abs()),
+                    negative: d.get() < 0,
+                }
+            }
+        }
+
+        impl FastDiv for $ty {
+            type PrecomputedDiv = $precomputed;
+
+            #[inline]
+            fn try_precompute_div(self) -> Result<Self::PrecomputedDiv, DivisorError> {
+                <$precomputed>::try_new(self)
+            }
+
+            #[inline]
+            fn fast_div(self, precomputed: Self::PrecomputedDiv) -> Self {
+                precomputed.div(self)
+            }
+
+            #[inline]
+            fn fast_mod(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                precomputed.rem(self, d)
+            }
+
+            #[inline]
+            fn is_multiple_of(self, precomputed: Self::PrecomputedDiv) -> bool {
+                precomputed.is_multiple_of(self)
+            }
+
+            #[inline]
+            fn fast_div_rem(self, precomputed: Self::PrecomputedDiv, d: Self) -> (Self, Self) {
+                precomputed.div_rem(self, d)
+            }
+
+            #[inline]
+            fn fast_div_ceil(self, precomputed: Self::PrecomputedDiv) -> Self {
+                let q = precomputed.div(self);
+                let inexact = !precomputed.is_multiple_of(self);
+                q + (inexact && (self < 0) == precomputed.negative) as Self
             }
+
+            // the rounding is done on the magnitude of the quotient, which is symmetric around zero
+            #[inline]
+            fn fast_div_round(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                let (q, r) = precomputed.div_rem(self, d);
+                let (r, d) = (r.unsigned_abs(), d.unsigned_abs());
+                let away = (r >= d - r) as Self;
+                if (self < 0) != precomputed.negative {
+                    q - away
+                } else {
+                    q + away
+                }
+            }
+
+            #[inline]
+            fn fast_div_round_half_even(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                let (q, r) = precomputed.div_rem(self, d);
+                let (r, d) = (r.unsigned_abs(), d.unsigned_abs());
+                let away = (r > d - r || (r == d - r && q & 1 != 0)) as Self;
+                if (self < 0) != precomputed.negative {
+                    q - away
+                } else {
+                    q + away
+                }
+            }
+
+            #[inline]
+            fn fast_div_euclid(self, precomputed: Self::PrecomputedDiv) -> Self {
+                let q = self.fast_div(precomputed);
+                // the truncated remainder is negative, so move the quotient away from it
+                let adjust = (self < 0 && !self.is_multiple_of(precomputed)) as Self;
+                if precomputed.negative {
+                    q + adjust
+                } else {
+                    q - adjust
+                }
+            }
+
+            #[inline]
+            fn fast_rem_euclid(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                let r = self.fast_mod(precomputed, d);
+                if r < 0 {
+                    r.wrapping_add(d.unsigned_abs() as Self)
+                } else {
+                    r
+                }
+            }
+
+            #[inline]
+            fn fast_div_floor(self, precomputed: Self::PrecomputedDiv) -> Self {
+                precomputed.div_floor(self)
+            }
+
+            #[inline]
+            fn fast_mod_floor(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                precomputed.mod_floor(self, d)
+            }
+        }
+    };
+}
+
+impl_fast_div_signed!(
+    i32,
+    NonZeroI32,
+    PrecomputedDivI32,
+    compute_m_u32,
+    compute_d_u32,
+    fastdiv_i32,
+    fastmod_i32,
+    is_divisible_i32
+);
+impl_fast_div_signed!(
+    i64,
+    NonZeroI64,
+    PrecomputedDivI64,
+    compute_m_u64,
+    compute_d_u64,
+    fastdiv_i64,
+    fastmod_i64,
+    is_divisible_i64
+);
+
+// formatting and the `PrecomputedDivisor` remainder go through the recovered divisor. `Hash` is
+// derived from the factor, which is consistent with `Eq` since the factor determines the divisor.
+// ordering compares `$key`, built from the factor alone: `m` strictly decreases as the magnitude
+// of the divisor grows, so it is inverted for positive divisors, and negative divisors sort first
+// in increasing `m`.
+macro_rules! impl_precomputed_traits {
+    ($($precomputed: ident => $ty: ty, |$p: ident| $key: expr),*) => {$(
+        impl PrecomputedDivisor for $precomputed {
+            type Int = $ty;
+
+            #[inline]
+            fn div(&self, n: $ty) -> $ty {
+                <$precomputed>::div(self, n)
+            }
+
+            #[inline]
+            fn rem(&self, n: $ty) -> $ty {
+                let q = <$precomputed>::div(self, n);
+                n.wrapping_sub(q.wrapping_mul(<$precomputed>::divisor(self)))
+            }
+
+            #[inline]
+            fn is_multiple(&self, n: $ty) -> bool {
+                <$precomputed>::is_multiple_of(self, n)
+            }
+
+            #[inline]
+            fn divisor(&self) -> $ty {
+                <$precomputed>::divisor(self)
+            }
+        }
+
+        impl core::fmt::Debug for $precomputed {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(stringify!($precomputed))
+                    .field("divisor", &self.divisor())
+                    .finish()
+            }
+        }
+
+        impl core::fmt::Display for $precomputed {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Display::fmt(&self.divisor(), f)
+            }
+        }
+
+        impl PartialOrd for $precomputed {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $precomputed {
+            #[inline]
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                let key = |$p: &Self| $key;
+                key(self).cmp(&key(other))
+            }
+        }
+    )*};
+}
+
+impl_precomputed_traits!(
+    PrecomputedDivU8 => u8, |p| !p.m,
+    PrecomputedDivU16 => u16, |p| !p.m,
+    PrecomputedDivU32 => u32, |p| !p.m,
+    PrecomputedDivU64 => u64, |p| !p.m,
+    PrecomputedDivU128 => u128, |p| (!p.m[1], !p.m[0]),
+    PrecomputedDivI32 => i32, |p| (!p.negative, if p.negative { p.m } else { !p.m }),
+    PrecomputedDivI64 => i64, |p| (!p.negative, if p.negative { p.m } else { !p.m })
+);
+
+// forwards every operation to the fixed-width type with the same layout
+macro_rules! impl_fast_div_pointer_width {
+    ($ty: ty, $nonzero: ty, $repr: ty, $precomputed: ty) => {
+        impl From<$nonzero> for $precomputed {
+            #[inline]
+            fn from(d: $nonzero) -> Self {
+                match (d.get() as $repr).try_precompute_div() {
+                    Ok(precomputed) => precomputed,
+                    Err(_) => unreachable!(),
+                }
+            }
+        }
+
+        impl FastDiv for $ty {
+            type PrecomputedDiv = $precomputed;
+
+            #[inline]
+            fn try_precompute_div(self) -> Result<Self::PrecomputedDiv, DivisorError> {
+                (self as $repr).try_precompute_div()
+            }
+
+            #[inline]
+            fn fast_div(self, precomputed: Self::PrecomputedDiv) -> Self {
+                (self as $repr).fast_div(precomputed) as Self
+            }
+
+            #[inline]
+            fn fast_mod(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                (self as $repr).fast_mod(precomputed, d as $repr) as Self
+            }
+
+            #[inline]
+            fn is_multiple_of(self, precomputed: Self::PrecomputedDiv) -> bool {
+                FastDiv::is_multiple_of(self as $repr, precomputed)
+            }
+
+            #[inline]
+            fn fast_div_rem(self, precomputed: Self::PrecomputedDiv, d: Self) -> (Self, Self) {
+                let (q, r) = (self as $repr).fast_div_rem(precomputed, d as $repr);
+                (q as Self, r as Self)
+            }
+
+            #[inline]
+            fn fast_div_ceil(self, precomputed: Self::PrecomputedDiv) -> Self {
+                (self as $repr).fast_div_ceil(precomputed) as Self
+            }
+
+            #[inline]
+            fn fast_div_round(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                (self as $repr).fast_div_round(precomputed, d as $repr) as Self
+            }
+
+            #[inline]
+            fn fast_div_round_half_even(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                (self as $repr).fast_div_round_half_even(precomputed, d as $repr) as Self
+            }
+
+            #[inline]
+            fn fast_div_euclid(self, precomputed: Self::PrecomputedDiv) -> Self {
+                (self as $repr).fast_div_euclid(precomputed) as Self
+            }
+
+            #[inline]
+            fn fast_rem_euclid(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                (self as $repr).fast_rem_euclid(precomputed, d as $repr) as Self
+            }
+
+            #[inline]
+            fn fast_div_floor(self, precomputed: Self::PrecomputedDiv) -> Self {
+                (self as $repr).fast_div_floor(precomputed) as Self
+            }
+
+            #[inline]
+            fn fast_mod_floor(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                (self as $repr).fast_mod_floor(precomputed, d as $repr) as Self
+            }
+
+            // the slice operations are forwarded as well, so that they use the batched paths of
+            // `$repr` instead of the scalar default loops
+            #[inline]
+            fn fast_div_slice(values: &mut [Self], precomputed: Self::PrecomputedDiv) {
+                <$repr>::fast_div_slice(Self::as_repr_mut(values), precomputed)
+            }
+
+            #[inline]
+            fn fast_mod_slice(values: &mut [Self], precomputed: Self::PrecomputedDiv, d: Self) {
+                <$repr>::fast_mod_slice(Self::as_repr_mut(values), precomputed, d as $repr)
+            }
+
+            #[inline]
+            fn fast_div_slice_into(
+                src: &[Self],
+                dst: &mut [Self],
+                precomputed: Self::PrecomputedDiv,
+            ) {
+                <$repr>::fast_div_slice_into(
+                    Self::as_repr(src),
+                    Self::as_repr_mut(dst),
+                    precomputed,
+                )
+            }
+
+            #[inline]
+            fn count_multiples(values: &[Self], precomputed: Self::PrecomputedDiv) -> usize {
+                <$repr>::count_multiples(Self::as_repr(values), precomputed)
+            }
+        }
+
+        impl PointerWidth for $ty {
+            type Repr = $repr;
+        }
+    };
+}
+
+// reinterprets pointer-width slices as slices of their fixed-width representation
+trait PointerWidth: Sized {
+    type Repr;
+
+    #[inline]
+    fn as_repr(values: &[Self]) -> &[Self::Repr] {
+        // SAFETY: `Repr` is the fixed-width integer of the same signedness, size and alignment,
+        // chosen by `target_pointer_width`
+        unsafe { core::slice::from_raw_parts(values.as_ptr().cast(), values.len()) }
+    }
+
+    #[inline]
+    fn as_repr_mut(values: &mut [Self]) -> &mut [Self::Repr] {
+        // SAFETY: `Repr` is the fixed-width integer of the same signedness, size and alignment,
+        // chosen by `target_pointer_width`
+        unsafe { core::slice::from_raw_parts_mut(values.as_mut_ptr().cast(), values.len()) }
+    }
+}
+
+impl_fast_div_pointer_width!(usize, NonZeroUsize, UsizeRepr, PrecomputedDivUsize);
+impl_fast_div_pointer_width!(isize, NonZeroIsize, IsizeRepr, PrecomputedDivIsize);
+
+/// A divisor bundled with its precomputed division factor, so that the two can never be
+/// mismatched.
+///
+/// # Example
+/// ```
+/// use fastdiv::Divisor;
+///
+/// let d = Divisor::new(7_u32);
+///
+/// assert_eq!(d.divisor(), 7);
+/// assert_eq!(d.div(23), 23 / 7);
+/// assert_eq!(d.rem(23), 23 % 7);
+/// assert_eq!(d.div_rem(23), (23 / 7, 23 % 7));
+/// assert!(d.is_multiple_of(21));
+///
+/// // the usual operators are also available
+/// assert_eq!(23 / d, 23 / 7);
+/// assert_eq!(23 % d, 23 % 7);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Divisor<T: FastDiv> {
+    precomputed: T::PrecomputedDiv,
+    divisor: T,
+}
+
+impl<T: FastDiv> Divisor<T> {
+    /// Precompute the division factor for the divisor `d`.
+    ///
+    /// # Panics
+    /// Panics if `d` is not a valid divisor, see [`FastDiv::try_precompute_div`].
+    #[inline]
+    pub fn new(d: T) -> Self {
+        Self {
+            precomputed: d.precompute_div(),
+            divisor: d,
+        }
+    }
+
+    /// Precompute the division factor for the divisor `d`, or return an error if `d` is not a
+    /// valid divisor.
+    #[inline]
+    pub fn try_new(d: T) -> Result<Self, DivisorError> {
+        Ok(Self {
+            precomputed: d.try_precompute_div()?,
+            divisor: d,
+        })
+    }
+
+    /// Returns the divisor.
+    #[inline]
+    pub fn divisor(&self) -> T {
+        self.divisor
+    }
+
+    /// Returns the precomputed division factor.
+    #[inline]
+    pub fn precomputed(&self) -> T::PrecomputedDiv {
+        self.precomputed
+    }
+
+    /// Divide `n` by the divisor.
+    #[inline]
+    pub fn div(&self, n: T) -> T {
+        n.fast_div(self.precomputed)
+    }
+
+    /// Compute the remainder of the division of `n` by the divisor.
+    #[inline]
+    pub fn rem(&self, n: T) -> T {
+        n.fast_mod(self.precomputed, self.divisor)
+    }
+
+    /// Compute both the quotient and the remainder of the division of `n` by the divisor.
+    #[inline]
+    pub fn div_rem(&self, n: T) -> (T, T) {
+        n.fast_div_rem(self.precomputed, self.divisor)
+    }
+
+    /// Check if `n` is a multiple of the divisor.
+    #[inline]
+    pub fn is_multiple_of(&self, n: T) -> bool {
+        FastDiv::is_multiple_of(n, self.precomputed)
+    }
+}
+
+impl<T: FastDiv + PartialEq> PartialEq for Divisor<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.divisor == other.divisor
+    }
+}
+
+impl<T: FastDiv + Eq> Eq for Divisor<T> {}
+
+impl<T: FastDiv + core::fmt::Debug> core::fmt::Debug for Divisor<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Divisor")
+            .field("divisor", &self.divisor)
+            .finish()
+    }
+}
+
+impl<T: FastDiv> PrecomputedDivisor for Divisor<T> {
+    type Int = T;
+
+    #[inline]
+    fn div(&self, n: T) -> T {
+        Divisor::div(self, n)
+    }
+
+    #[inline]
+    fn rem(&self, n: T) -> T {
+        Divisor::rem(self, n)
+    }
+
+    #[inline]
+    fn is_multiple(&self, n: T) -> bool {
+        Divisor::is_multiple_of(self, n)
+    }
+
+    #[inline]
+    fn divisor(&self) -> T {
+        self.divisor
+    }
+}
+
+macro_rules! impl_divisor_ops {
+    ($($ty: ty),*) => {$(
+        impl core::ops::Div<Divisor<$ty>> for $ty {
+            type Output = $ty;
+
+            #[inline]
+            fn div(self, rhs: Divisor<$ty>) -> Self::Output {
+                rhs.div(self)
+            }
+        }
+
+        impl core::ops::Rem<Divisor<$ty>> for $ty {
+            type Output = $ty;
+
+            #[inline]
+            fn rem(self, rhs: Divisor<$ty>) -> Self::Output {
+                rhs.rem(self)
+            }
+        }
+
+        impl core::ops::DivAssign<Divisor<$ty>> for $ty {
+            #[inline]
+            fn div_assign(&mut self, rhs: Divisor<$ty>) {
+                *self = rhs.div(*self);
+            }
+        }
+
+        impl core::ops::RemAssign<Divisor<$ty>> for $ty {
+            #[inline]
+            fn rem_assign(&mut self, rhs: Divisor<$ty>) {
+                *self = rhs.rem(*self);
+            }
+        }
+    )*};
+}
+
+impl_divisor_ops!(u8, u16, u32, u64, u128, usize, i32, i64, isize);
+
+const MAGIC_ADD_MARKER: u8 = 0x40;
+const MAGIC_SHIFT_MASK: u8 = 0x3F;
+
+#[inline]
+const fn mulhi_u64(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) >> 64) as u64
+}
+#[inline]
+const fn mulhi_u32(a: u32, b: u32) -> u32 {
+    ((a as u64 * b as u64) >> 32) as u32
+}
+// returns (magic, more), with a zero magic for powers of two. the branchfree variant always
+// uses the add-and-shift form and stores a shift that is one less for powers of two.
+#[inline]
+const fn compute_magic_u32(d: u32, branchfree: bool) -> (u32, u8) {
+    let floor_log_2_d = 31 - d.leading_zeros();
+    if d.is_power_of_two() {
+        return (0, (floor_log_2_d - branchfree as u32) as u8);
+    }
+    let numerator = 1_u64 << (32 + floor_log_2_d);
+    let proposed_m = (numerator / d as u64) as u32;
+    let rem = (numerator % d as u64) as u32;
+    let e = d - rem;
+    if !branchfree && e < (1 << floor_log_2_d) {
+        // 2^floor_log_2_d works as the power, and the magic number fits in 32 bits
+        (proposed_m.wrapping_add(1), floor_log_2_d as u8)
+    } else {
+        // the magic number needs 33 bits, compensate with an add and a shift in the division
+        let mut proposed_m = proposed_m.wrapping_add(proposed_m);
+        let twice_rem = rem.wrapping_add(rem);
+        if twice_rem >= d || twice_rem < rem {
+            proposed_m = proposed_m.wrapping_add(1);
+        }
+        (
+            proposed_m.wrapping_add(1),
+            floor_log_2_d as u8 | MAGIC_ADD_MARKER,
+        )
+    }
+}
+#[inline]
+const fn compute_magic_u64(d: u64, branchfree: bool) -> (u64, u8) {
+    let floor_log_2_d = 63 - d.leading_zeros();
+    if d.is_power_of_two() {
+        return (0, (floor_log_2_d - branchfree as u32) as u8);
+    }
+    let numerator = 1_u128 << (64 + floor_log_2_d);
+    let proposed_m = (numerator / d as u128) as u64;
+    let rem = (numerator % d as u128) as u64;
+    let e = d - rem;
+    if !branchfree && e < (1 << floor_log_2_d) {
+        // 2^floor_log_2_d works as the power, and the magic number fits in 64 bits
+        (proposed_m.wrapping_add(1), floor_log_2_d as u8)
+    } else {
+        // the magic number needs 65 bits, compensate with an add and a shift in the division
+        let mut proposed_m = proposed_m.wrapping_add(proposed_m);
+        let twice_rem = rem.wrapping_add(rem);
+        if twice_rem >= d || twice_rem < rem {
+            proposed_m = proposed_m.wrapping_add(1);
+        }
+        (
+            proposed_m.wrapping_add(1),
+            floor_log_2_d as u8 | MAGIC_ADD_MARKER,
+        )
+    }
+}
+#[inline]
+const fn magicdiv_u64(a: u64, magic: u64, more: u8) -> u64 {
+    if magic == 0 {
+        return a >> more;
+    }
+    let q = mulhi_u64(magic, a);
+    if more & MAGIC_ADD_MARKER != 0 {
+        let t = ((a - q) >> 1) + q;
+        t >> (more & MAGIC_SHIFT_MASK)
+    } else {
+        q >> more
+    }
+}
+
+/// Precomputed division factor for a `u64` divisor, using the Granlund-Montgomery magic number
+/// and shift instead of Lemire's 128-bit factor.
+///
+/// Each division only needs a single 64x64 bit high multiplication, which is cheaper than the
+/// 128-bit arithmetic of [`PrecomputedDivU64`], at the cost of a slower precomputation. It has
+/// the same operations as [`PrecomputedDivU64`], and both implement [`PrecomputedDivisor`], so
+/// generic code can switch between the two strategies.
+///
+/// # Example
+/// ```
+/// use fastdiv::{MagicDivU64, PrecomputedDivU64, PrecomputedDivisor};
+///
+/// fn sum_of_quotients<D: PrecomputedDivisor<Int = u64>>(values: &[u64], d: &D) -> u64 {
+///     values.iter().map(|&n| d.div(n)).sum()
+/// }
+///
+/// let d: u64 = 7;
+/// let m = MagicDivU64::new(d);
+///
+/// assert_eq!(m.div(1 << 40), (1 << 40) / d);
+/// assert_eq!(m.rem(1 << 40, d), (1 << 40) % d);
+/// assert!(m.is_multiple_of(7 << 40));
+/// assert_eq!(sum_of_quotients(&[10, 20, 35], &m), 8);
+/// assert_eq!(sum_of_quotients(&[10, 20, 35], &PrecomputedDivU64::new(d)), 8);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct MagicDivU64 {
+    magic: u64,
+    more: u8,
+    // the magic number does not give the divisor back cheaply
+    divisor: u64,
+}
+
+impl MagicDivU64 {
+    /// Precompute the division factor for the divisor `d`.
+    ///
+    /// # Panics
+    /// Panics if `d` is zero.
+    #[inline]
+    pub const fn new(d: u64) -> Self {
+        assert!(d != 0, "divisor is zero");
+        let (magic, more) = compute_magic_u64(d, false);
+        Self {
+            magic,
+            more,
+            divisor: d,
+        }
+    }
+
+    /// Precompute the division factor for the divisor `d`, or return an error if `d` is zero.
+    #[inline]
+    pub const fn try_new(d: u64) -> Result<Self, DivisorError> {
+        if d == 0 {
+            return Err(DivisorError::Zero);
+        }
+        Ok(Self::new(d))
+    }
+
+    /// Returns the divisor.
+    #[inline]
+    pub const fn divisor(&self) -> u64 {
+        self.divisor
+    }
+
+    /// Divide `n` by the divisor.
+    #[inline]
+    pub const fn div(&self, n: u64) -> u64 {
+        magicdiv_u64(n, self.magic, self.more)
+    }
+
+    /// Compute the remainder of the division of `n` by the divisor `d`.
+    /// If `self` does not come from the same provided divisor, the result is unspecified.
+    #[inline]
+    pub const fn rem(&self, n: u64, d: u64) -> u64 {
+        self.div_rem(n, d).1
+    }
+
+    /// Compute both the quotient and the remainder of the division of `n` by the divisor `d`.
+    /// If `self` does not come from the same provided divisor, the result is unspecified.
+    #[inline]
+    pub const fn div_rem(&self, n: u64, d: u64) -> (u64, u64) {
+        let q = self.div(n);
+        (q, n.wrapping_sub(q.wrapping_mul(d)))
+    }
+
+    /// Check if `n` is a multiple of the divisor.
+    #[inline]
+    pub const fn is_multiple_of(&self, n: u64) -> bool {
+        self.rem(n, self.divisor) == 0
+    }
+}
+
+impl From<NonZeroU64> for MagicDivU64 {
+    #[inline]
+    fn from(d: NonZeroU64) -> Self {
+        Self::new(d.get())
+    }
+}
+
+impl PrecomputedDivisor for MagicDivU64 {
+    type Int = u64;
+
+    #[inline]
+    fn div(&self, n: u64) -> u64 {
+        Self::div(self, n)
+    }
+
+    #[inline]
+    fn rem(&self, n: u64) -> u64 {
+        Self::rem(self, n, self.divisor)
+    }
+
+    #[inline]
+    fn is_multiple(&self, n: u64) -> bool {
+        Self::is_multiple_of(self, n)
+    }
+
+    #[inline]
+    fn divisor(&self) -> u64 {
+        self.divisor
+    }
+}
+
+// the same code path for every divisor, for d > 1
+#[inline]
+const fn branchfree_div_u32(a: u32, magic: u32, shift: u8) -> u32 {
+    let q = mulhi_u32(magic, a);
+    let t = ((a - q) >> 1) + q;
+    t >> shift
+}
+#[inline]
+const fn branchfree_div_u64(a: u64, magic: u64, shift: u8) -> u64 {
+    let q = mulhi_u64(magic, a);
+    let t = ((a - q) >> 1) + q;
+    t >> shift
+}
+
+macro_rules! impl_branchfree_div {
+    ($ty: ty, $nonzero: ty, $branchfree: ident, $compute_magic: ident, $div: ident) => {
+        impl $branchfree {
+            /// Precompute the division factor for the divisor `d`.
+            ///
+            /// # Panics
+            /// Panics if `d` is zero or one.
+            #[inline]
+            pub const fn new(d: $ty) -> Self {
+                assert!(d != 0, "divisor is zero");
+                assert!(d != 1, "divisor is not supported");
+                let (magic, more) = $compute_magic(d, true);
+                Self {
+                    magic,
+                    shift: more & MAGIC_SHIFT_MASK,
+                    divisor: d,
+                }
+            }
+
+            /// Precompute the division factor for the divisor `d`, or return an error if `d` is
+            /// zero or one.
+            #[inline]
+            pub const fn try_new(d: $ty) -> Result<Self, DivisorError> {
+                match d {
+                    0 => Err(DivisorError::Zero),
+                    1 => Err(DivisorError::Unsupported),
+                    _ => Ok(Self::new(d)),
+                }
+            }
+
+            /// Returns the divisor.
+            #[inline]
+            pub const fn divisor(&self) -> $ty {
+                self.divisor
+            }
+
+            /// Divide `n` by the divisor.
+            #[inline]
+            pub const fn div(&self, n: $ty) -> $ty {
+                $div(n, self.magic, self.shift)
+            }
+
+            /// Compute the remainder of the division of `n` by the divisor `d`.
+            /// If `self` does not come from the same provided divisor, the result is unspecified.
+            #[inline]
+            pub const fn rem(&self, n: $ty, d: $ty) -> $ty {
+                self.div_rem(n, d).1
+            }
+
+            /// Compute both the quotient and the remainder of the division of `n` by the divisor
+            /// `d`.
+            /// If `self` does not come from the same provided divisor, the result is unspecified.
+            #[inline]
+            pub const fn div_rem(&self, n: $ty, d: $ty) -> ($ty, $ty) {
+                let q = self.div(n);
+                (q, n.wrapping_sub(q.wrapping_mul(d)))
+            }
+
+            /// Check if `n` is a multiple of the divisor.
+            #[inline]
+            pub const fn is_multiple_of(&self, n: $ty) -> bool {
+                self.rem(n, self.divisor) == 0
+            }
+        }
+
+        // the divisor `1` is not supported, so unlike the other factors this conversion can fail
+        impl TryFrom<$nonzero> for $branchfree {
+            type Error = DivisorError;
+
+            #[inline]
+            fn try_from(d: $nonzero) -> Result<Self, DivisorError> {
+                Self::try_new(d.get())
+            }
+        }
+
+        impl PrecomputedDivisor for $branchfree {
+            type Int = $ty;
+
+            #[inline]
+            fn div(&self, n: $ty) -> $ty {
+                Self::div(self, n)
+            }
+
+            #[inline]
+            fn rem(&self, n: $ty) -> $ty {
+                Self::rem(self, n, self.divisor)
+            }
+
+            #[inline]
+            fn is_multiple(&self, n: $ty) -> bool {
+                Self::is_multiple_of(self, n)
+            }
+
+            #[inline]
+            fn divisor(&self) -> $ty {
+                self.divisor
+            }
+        }
+    };
+}
+
+/// Precomputed division factor for a `u32` divisor, using the branch-free algorithm from
+/// libdivide.
+///
+/// Every divisor goes through the same multiply, add and shift sequence, which makes it a better
+/// fit than [`PrecomputedDivU32`] for vectorized code. The divisor `1` is not supported.
+///
+/// It has the same operations as [`PrecomputedDivU32`], and both implement
+/// [`PrecomputedDivisor`], so a call site can be written once and pick either strategy.
+///
+/// # Example
+/// ```
+/// use fastdiv::{BranchFreeDivU32, PrecomputedDivU32, PrecomputedDivisor};
+///
+/// fn count_multiples<D: PrecomputedDivisor<Int = u32>>(values: &[u32], d: &D) -> usize {
+///     values.iter().filter(|&&n| d.is_multiple(n)).count()
+/// }
+///
+/// let d: u32 = 7;
+/// let m = BranchFreeDivU32::new(d);
+///
+/// assert_eq!(m.div(100), 100 / d);
+/// assert_eq!(m.rem(100, d), 100 % d);
+/// assert_eq!(count_multiples(&[7, 10, 14], &m), 2);
+/// assert_eq!(count_multiples(&[7, 10, 14], &PrecomputedDivU32::new(d)), 2);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct BranchFreeDivU32 {
+    magic: u32,
+    shift: u8,
+    divisor: u32,
+}
+/// Precomputed division factor for a `u64` divisor, using the branch-free algorithm from
+/// libdivide.
+///
+/// Every divisor goes through the same multiply, add and shift sequence, which makes it a better
+/// fit than [`PrecomputedDivU64`] for vectorized code. The divisor `1` is not supported.
+///
+/// Like [`BranchFreeDivU32`], it implements [`PrecomputedDivisor`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct BranchFreeDivU64 {
+    magic: u64,
+    shift: u8,
+    divisor: u64,
+}
+
+impl_branchfree_div!(
+    u32,
+    NonZeroU32,
+    BranchFreeDivU32,
+    compute_magic_u32,
+    branchfree_div_u32
+);
+impl_branchfree_div!(
+    u64,
+    NonZeroU64,
+    BranchFreeDivU64,
+    compute_magic_u64,
+    branchfree_div_u64
+);
+
+// inverse of an odd `d` modulo 2^32, using Newton's iteration. `d` is its own inverse modulo
+// 2^3, and each step doubles the number of correct bits.
+#[inline]
+const fn modular_inverse_u32(d: u32) -> u32 {
+    let mut inv = d;
+    let mut i = 0;
+    while i < 4 {
+        inv = inv.wrapping_mul(2_u32.wrapping_sub(d.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv
+}
+#[inline]
+const fn modular_inverse_u64(d: u64) -> u64 {
+    let mut inv = d;
+    let mut i = 0;
+    while i < 5 {
+        inv = inv.wrapping_mul(2_u64.wrapping_sub(d.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv
+}
+
+macro_rules! impl_exact_div {
+    ($ty: ty, $nonzero: ty, $exact: ty, $inverse: ident) => {
+        impl $exact {
+            /// Precompute the modular inverse for the divisor `d`.
+            ///
+            /// # Panics
+            /// Panics if `d` is zero.
+            #[inline]
+            pub const fn new(d: $ty) -> Self {
+                assert!(d != 0, "divisor is zero");
+                let shift = d.trailing_zeros();
+                Self {
+                    inv: $inverse(d >> shift),
+                    shift,
+                }
+            }
+
+            /// Precompute the modular inverse for the divisor `d`, or return an error if `d` is
+            /// zero.
+            #[inline]
+            pub const fn try_new(d: $ty) -> Result<Self, DivisorError> {
+                if d == 0 {
+                    return Err(DivisorError::Zero);
+                }
+                Ok(Self::new(d))
+            }
+
+            /// Divide `n` by the divisor, assuming that `n` is a multiple of the divisor.
+            /// If `n` is not a multiple of the divisor, the result is unspecified.
+            #[inline]
+            pub const fn div_exact(&self, n: $ty) -> $ty {
+                (n >> self.shift).wrapping_mul(self.inv)
+            }
+        }
+
+        impl From<$nonzero> for $exact {
+            #[inline]
+            fn from(d: $nonzero) -> Self {
+                Self::new(d.get())
+            }
+        }
+    };
+}
+
+/// Precomputed modular inverse for exact division of `u32` values by a divisor.
+///
+/// Dividing a multiple of the divisor only takes a shift and a wrapping multiplication. This pairs
+/// naturally with [`FastDiv::is_multiple_of`] when the divisibility isn't known in advance.
+///
+/// # Example
+/// ```
+/// use fastdiv::{FastDiv, PrecomputedExactDivU32};
+///
+/// let d: u32 = 24;
+/// let exact = PrecomputedExactDivU32::new(d);
+/// let m = d.precompute_div();
+///
+/// let n = 24 * 1000;
+/// assert!(FastDiv::is_multiple_of(n, m));
+/// assert_eq!(exact.div_exact(n), 1000);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct PrecomputedExactDivU32 {
+    inv: u32,
+    shift: u32,
+}
+/// Precomputed modular inverse for exact division of `u64` values by a divisor.
+///
+/// Dividing a multiple of the divisor only takes a shift and a wrapping multiplication. This pairs
+/// naturally with [`FastDiv::is_multiple_of`] when the divisibility isn't known in advance.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct PrecomputedExactDivU64 {
+    inv: u64,
+    shift: u32,
+}
+
+impl_exact_div!(u32, NonZeroU32, PrecomputedExactDivU32, modular_inverse_u32);
+impl_exact_div!(u64, NonZeroU64, PrecomputedExactDivU64, modular_inverse_u64);
+
+/// Precomputed divisibility test for `u64` values, using the modular inverse of the divisor.
+///
+/// Unlike [`FastDiv::is_multiple_of`] with a [`PrecomputedDivU64`], which needs 128-bit
+/// arithmetic, the test only takes a wrapping 64-bit multiplication, a rotation and a
+/// comparison.
+///
+/// # Example
+/// ```
+/// use fastdiv::PrecomputedDivisibilityU64;
+///
+/// let m = PrecomputedDivisibilityU64::new(12);
+///
+/// assert!(m.is_multiple_of(36));
+/// assert!(!m.is_multiple_of(38));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct PrecomputedDivisibilityU64 {
+    inv: u64,
+    limit: u64,
+    shift: u32,
+}
+
+impl PrecomputedDivisibilityU64 {
+    /// Precompute the divisibility test for the divisor `d`.
+    ///
+    /// # Panics
+    /// Panics if `d` is zero.
+    #[inline]
+    pub const fn new(d: u64) -> Self {
+        assert!(d != 0, "divisor is zero");
+        let shift = d.trailing_zeros();
+        Self {
+            inv: modular_inverse_u64(d >> shift),
+            limit: u64::MAX / d,
+            shift,
+        }
+    }
+
+    /// Precompute the divisibility test for the divisor `d`, or return an error if `d` is zero.
+    #[inline]
+    pub const fn try_new(d: u64) -> Result<Self, DivisorError> {
+        if d == 0 {
+            return Err(DivisorError::Zero);
+        }
+        Ok(Self::new(d))
+    }
+
+    /// Check if `n` is a multiple of the divisor.
+    #[inline]
+    pub const fn is_multiple_of(&self, n: u64) -> bool {
+        // multiples of the divisor are exactly the values that map to `0..=MAX / d`, the rotation
+        // moves the low bits that must be zero for the power of two factor to the top
+        n.wrapping_mul(self.inv).rotate_right(self.shift) <= self.limit
+    }
+}
+
+impl From<NonZeroU64> for PrecomputedDivisibilityU64 {
+    #[inline]
+    fn from(d: NonZeroU64) -> Self {
+        Self::new(d.get())
+    }
+}
+
+/// A divisor known at compile time, with the same operations as the precomputed division factors.
+///
+/// This is a zero-sized type that uses the native `/` and `%` operators, which the compiler
+/// already optimizes for constant divisors. It implements [`PrecomputedDivisor`], so generic code
+/// can be instantiated with either a compile-time or a runtime divisor.
+///
+/// The divisor `D` must be nonzero and fit in `T`. This is checked at compile time, and using
+/// the operations of an invalid divisor fails to compile.
+///
+/// # Example
+/// ```
+/// use fastdiv::{ConstDiv, Divisor, PrecomputedDivisor};
+///
+/// fn sum_of_quotients<D: PrecomputedDivisor<Int = u32>>(values: &[u32], d: &D) -> u32 {
+///     values.iter().map(|&n| d.div(n)).sum()
+/// }
+///
+/// let values = [10, 20, 35];
+/// assert_eq!(sum_of_quotients(&values, &ConstDiv::<7, u32>::new()), 8);
+/// assert_eq!(sum_of_quotients(&values, &Divisor::new(7)), 8);
+///
+/// const D: ConstDiv<10> = ConstDiv::new();
+/// assert_eq!(D.div_rem(1234), (123, 4));
+/// ```
+///
+/// A divisor that does not fit is rejected:
+/// ```compile_fail
+/// use fastdiv::ConstDiv;
+///
+/// let q = ConstDiv::<256, u8>::new().div(100);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ConstDiv<const D: u64, T = u64>(PhantomData<T>);
+
+impl<const D: u64, T> ConstDiv<D, T> {
+    /// Create the divisor.
+    #[inline]
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+macro_rules! impl_const_div {
+    ($($ty: ty),*) => {$(
+        impl<const D: u64> ConstDiv<D, $ty> {
+            // evaluated when first used, so invalid divisors fail to compile
+            const DIVISOR: $ty = {
+                assert!(D != 0, "divisor is zero");
+                assert!(D <= <$ty>::MAX as u64, "divisor does not fit in the integer type");
+                D as $ty
+            };
+
+            /// Returns the divisor.
+            #[inline]
+            pub const fn divisor(&self) -> $ty {
+                Self::DIVISOR
+            }
+
+            /// Divide `n` by the divisor.
+            #[inline]
+            pub const fn div(&self, n: $ty) -> $ty {
+                n / Self::DIVISOR
+            }
+
+            /// Compute the remainder of the division of `n` by the divisor.
+            #[inline]
+            pub const fn rem(&self, n: $ty) -> $ty {
+                n % Self::DIVISOR
+            }
+
+            /// Compute both the quotient and the remainder of the division of `n` by the
+            /// divisor.
+            #[inline]
+            pub const fn div_rem(&self, n: $ty) -> ($ty, $ty) {
+                (n / Self::DIVISOR, n % Self::DIVISOR)
+            }
+
+            /// Check if `n` is a multiple of the divisor.
+            #[inline]
+            pub const fn is_multiple_of(&self, n: $ty) -> bool {
+                n % Self::DIVISOR == 0
+            }
+        }
+
+        impl<const D: u64> PrecomputedDivisor for ConstDiv<D, $ty> {
+            type Int = $ty;
+
+            #[inline]
+            fn div(&self, n: $ty) -> $ty {
+                Self::div(self, n)
+            }
+
+            #[inline]
+            fn rem(&self, n: $ty) -> $ty {
+                Self::rem(self, n)
+            }
+
+            #[inline]
+            fn is_multiple(&self, n: $ty) -> bool {
+                Self::is_multiple_of(self, n)
+            }
+
+            #[inline]
+            fn divisor(&self) -> $ty {
+                Self::DIVISOR
+            }
+        }
+    )*};
+}
+
+impl_const_div!(u8, u16, u32, u64, u128, usize, i32, i64, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // xorshift64 generator for pseudo-random test inputs
+    pub(crate) fn xorshift() -> impl FnMut() -> u64 {
+        let mut x: u64 = 0x2545F4914F6CDD1D;
+        move || {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x
+        }
+    }
+
+    #[test]
+    fn div_u8() {
+        for j in 1..=u8::MAX {
+            let p = j.precompute_div();
+            for i in 0..=u8::MAX {
+                assert_eq!(i.fast_mod(p, j), i % j);
+                assert_eq!(i.fast_div(p), i / j);
+                assert_eq!(i.fast_div_rem(p, j), (i / j, i % j));
+                assert_eq!(FastDiv::is_multiple_of(i, p), i % j == 0);
+            }
+        }
+    }
+
+    #[test]
+    fn div_u16() {
+        let n: u16 = 1000;
+        for j in 1..n {
+            let p = j.precompute_div();
+            for i in (0..n).chain(u16::MAX - n..=u16::MAX) {
+                assert_eq!(i.fast_mod(p, j), i % j);
+                assert_eq!(i.fast_div(p), i / j);
+                assert_eq!(i.fast_div_rem(p, j), (i / j, i % j));
+                assert_eq!(FastDiv::is_multiple_of(i, p), i % j == 0);
+            }
+        }
+        for j in u16::MAX - n..=u16::MAX {
+            let p = j.precompute_div();
+            for i in 0..=u16::MAX {
+                assert_eq!(i.fast_mod(p, j), i % j);
+                assert_eq!(i.fast_div(p), i / j);
+                assert_eq!(i.fast_div_rem(p, j), (i / j, i % j));
+                assert_eq!(FastDiv::is_multiple_of(i, p), i % j == 0);
+            }
+        }
+    }
+
+    #[test]
+    fn div_u32() {
+        let n: u32 = 1000;
+        for j in 1..n {
+            let p = j.precompute_div();
+            for i in 0..n {
+                assert_eq!(i.fast_mod(p, j), i % j);
+                assert_eq!(i.fast_div(p), i / j);
+                assert_eq!(i.fast_div_rem(p, j), (i / j, i % j));
+                assert_eq!(FastDiv::is_multiple_of(i, p), i % j == 0);
+            }
+        }
+    }
+
+    #[test]
+    fn div_u64() {
+        let n: u64 = 1000;
+        for j in 1..n {
+            let p = j.precompute_div();
+            for i in 0..n {
+                assert_eq!(i.fast_mod(p, j), i % j);
+                assert_eq!(i.fast_div(p), i / j);
+                assert_eq!(i.fast_div_rem(p, j), (i / j, i % j));
+                assert_eq!(FastDiv::is_multiple_of(i, p), i % j == 0);
+            }
+        }
+    }
+
+    #[test]
+    fn div_u128() {
+        let n: u128 = 1000;
+        for j in 1..n {
+            let p = j.precompute_div();
+            for i in 0..n {
+                assert_eq!(i.fast_mod(p, j), i % j);
+                assert_eq!(i.fast_div(p), i / j);
+                assert_eq!(i.fast_div_rem(p, j), (i / j, i % j));
+                assert_eq!(FastDiv::is_multiple_of(i, p), i % j == 0);
+            }
+        }
+
+        let mut rng = xorshift();
+        let mut next = || (rng() as u128) << 64 | rng() as u128;
+        for _ in 0..1000 {
+            let shift = next() % 127;
+            let j = (next() >> shift).max(1);
+            let p = j.precompute_div();
+            for i in [
+                0,
+                1,
+                j - 1,
+                j,
+                j.wrapping_add(1),
+                u128::MAX - 1,
+                u128::MAX,
+                next(),
+                next().wrapping_mul(j),
+            ] {
+                assert_eq!(i.fast_mod(p, j), i % j);
+                assert_eq!(i.fast_div(p), i / j);
+                assert_eq!(i.fast_div_rem(p, j), (i / j, i % j));
+                assert_eq!(FastDiv::is_multiple_of(i, p), i % j == 0);
+            }
+        }
+    }
+
+    #[test]
+    fn div_usize() {
+        let n: usize = 1000;
+        for j in (1..n).chain([usize::MAX - 1, usize::MAX]) {
+            let p: PrecomputedDivUsize = j.precompute_div();
+            for i in (0..n).chain([usize::MAX - 1, usize::MAX]) {
+                assert_eq!(i.fast_mod(p, j), i % j);
+                assert_eq!(i.fast_div(p), i / j);
+                assert_eq!(i.fast_div_rem(p, j), (i / j, i % j));
+                assert_eq!(FastDiv::is_multiple_of(i, p), i % j == 0);
+            }
+        }
+    }
+
+    #[test]
+    fn div_i32() {
+        let n: i32 = 1000;
+        let divisors = (-n..n).chain([i32::MIN, i32::MIN + 1, i32::MAX - 1, i32::MAX]);
+        for j in divisors.filter(|&j| j != 0) {
+            let p = j.precompute_div();
+            for i in (-n..n).chain([i32::MIN, i32::MIN + 1, i32::MAX - 1, i32::MAX]) {
+                assert_eq!(i.fast_mod(p, j), i.wrapping_rem(j));
+                assert_eq!(i.fast_div(p), i.wrapping_div(j));
+                assert_eq!(i.fast_div_rem(p, j), (i.wrapping_div(j), i.wrapping_rem(j)));
+                assert_eq!(i.is_multiple_of(p), i.wrapping_rem(j) == 0);
+            }
+        }
+    }
+
+    #[test]
+    fn div_i64() {
+        let n: i64 = 1000;
+        let divisors = (-n..n).chain([i64::MIN, i64::MIN + 1, i64::MAX - 1, i64::MAX]);
+        for j in divisors.filter(|&j| j != 0) {
+            let p = j.precompute_div();
+            for i in (-n..n).chain([i64::MIN, i64::MIN + 1, i64::MAX - 1, i64::MAX]) {
+                assert_eq!(i.fast_mod(p, j), i.wrapping_rem(j));
+                assert_eq!(i.fast_div(p), i.wrapping_div(j));
+                assert_eq!(i.fast_div_rem(p, j), (i.wrapping_div(j), i.wrapping_rem(j)));
+                assert_eq!(i.is_multiple_of(p), i.wrapping_rem(j) == 0);
+            }
+        }
+    }
+
+    #[test]
+    fn div_isize() {
+        let n: isize = 1000;
+        let extremes = [isize::MIN, isize::MIN + 1, isize::MAX - 1, isize::MAX];
+        for j in (-n..n).chain(extremes).filter(|&j| j != 0) {
+            let p: PrecomputedDivIsize = j.precompute_div();
+            for i in (-n..n).chain(extremes) {
+                assert_eq!(i.fast_mod(p, j), i.wrapping_rem(j));
+                assert_eq!(i.fast_div(p), i.wrapping_div(j));
+                assert_eq!(i.fast_div_rem(p, j), (i.wrapping_div(j), i.wrapping_rem(j)));
+                assert_eq!(i.is_multiple_of(p), i.wrapping_rem(j) == 0);
+                assert_eq!(i.fast_div_euclid(p), i.wrapping_div_euclid(j));
+                assert_eq!(i.fast_rem_euclid(p, j), i.wrapping_rem_euclid(j));
+            }
+        }
+    }
+
+    #[test]
+    fn div_euclid_floor_i32() {
+        let n: i32 = 300;
+        let extremes = [i32::MIN, i32::MIN + 1, i32::MAX - 1, i32::MAX];
+        for j in (-n..n).chain(extremes).filter(|&j| j != 0) {
+            let p = j.precompute_div();
+            for i in (-n..n).chain(extremes) {
+                let floor_q =
+                    i.wrapping_div(j) - (i.wrapping_rem(j) != 0 && (i < 0) != (j < 0)) as i32;
+                assert_eq!(i.fast_div_euclid(p), i.wrapping_div_euclid(j));
+                assert_eq!(i.fast_rem_euclid(p, j), i.wrapping_rem_euclid(j));
+                assert_eq!(i.fast_div_floor(p), floor_q);
+                assert_eq!(
+                    i.fast_mod_floor(p, j),
+                    i.wrapping_sub(floor_q.wrapping_mul(j))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn div_euclid_floor_i64() {
+        let n: i64 = 300;
+        let extremes = [i64::MIN, i64::MIN + 1, i64::MAX - 1, i64::MAX];
+        for j in (-n..n).chain(extremes).filter(|&j| j != 0) {
+            let p = j.precompute_div();
+            for i in (-n..n).chain(extremes) {
+                let floor_q =
+                    i.wrapping_div(j) - (i.wrapping_rem(j) != 0 && (i < 0) != (j < 0)) as i64;
+                assert_eq!(i.fast_div_euclid(p), i.wrapping_div_euclid(j));
+                assert_eq!(i.fast_rem_euclid(p, j), i.wrapping_rem_euclid(j));
+                assert_eq!(i.fast_div_floor(p), floor_q);
+                assert_eq!(
+                    i.fast_mod_floor(p, j),
+                    i.wrapping_sub(floor_q.wrapping_mul(j))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn div_one() {
+        macro_rules! check {
+            ($($ty: ty),*) => {$(
+                let p = (1 as $ty).precompute_div();
+                for i in [<$ty>::MIN, <$ty>::MIN + 1, 0, 1, <$ty>::MAX - 1, <$ty>::MAX] {
+                    assert_eq!(i.fast_div(p), i);
+                    assert_eq!(i.fast_mod(p, 1), 0);
+                    assert!(FastDiv::is_multiple_of(i, p));
+                }
+            )*};
+        }
+        check!(u8, u16, u32, u64, u128, usize, i32, i64, isize);
+
+        assert_eq!(i32::MIN.fast_div((-1_i32).precompute_div()), i32::MIN);
+        assert_eq!(i64::MIN.fast_mod((-1_i64).precompute_div(), -1), 0);
+    }
+
+    #[test]
+    fn try_precompute_div() {
+        assert_eq!(0_u8.try_precompute_div().err(), Some(DivisorError::Zero));
+        assert_eq!(0_u32.try_precompute_div().err(), Some(DivisorError::Zero));
+        assert_eq!(0_u128.try_precompute_div().err(), Some(DivisorError::Zero));
+        assert_eq!(0_i64.try_precompute_div().err(), Some(DivisorError::Zero));
+        assert_eq!(0_usize.try_precompute_div().err(), Some(DivisorError::Zero));
+
+        for d in 1..1000_u32 {
+            assert_eq!(d.try_precompute_div(), Ok(d.precompute_div()));
+            let nonzero = NonZeroU32::new(d).unwrap();
+            assert!(PrecomputedDivU32::from(nonzero) == d.precompute_div());
+        }
+        for d in (-1000..1000_i64).filter(|&d| d != 0) {
+            assert_eq!(d.try_precompute_div(), Ok(d.precompute_div()));
+            let nonzero = NonZeroI64::new(d).unwrap();
+            assert!(PrecomputedDivI64::from(nonzero) == d.precompute_div());
+        }
+        let nonzero = NonZeroUsize::new(usize::MAX).unwrap();
+        assert!(PrecomputedDivUsize::from(nonzero) == usize::MAX.precompute_div());
+    }
+
+    #[test]
+    #[should_panic]
+    fn precompute_div_zero() {
+        0_u64.precompute_div();
+    }
+
+    #[test]
+    fn divisor() {
+        for j in 1..1000_u32 {
+            let d = Divisor::new(j);
+            assert_eq!(d.divisor(), j);
+            for i in 0..1000 {
+                assert_eq!(d.div(i), i / j);
+                assert_eq!(d.rem(i), i % j);
+                assert_eq!(d.div_rem(i), (i / j, i % j));
+                assert_eq!(d.is_multiple_of(i), i % j == 0);
+            }
+        }
+        for j in (-300..300_i64).filter(|&j| j != 0) {
+            let d = Divisor::new(j);
+            for i in -300..300 {
+                assert_eq!(d.div_rem(i), (i / j, i % j));
+                assert_eq!(d.is_multiple_of(i), i % j == 0);
+            }
+        }
+        assert_eq!(Divisor::try_new(0_u16), Err(DivisorError::Zero));
+        assert_eq!(Divisor::try_new(5_u16), Ok(Divisor::new(5)));
+    }
+
+    #[test]
+    fn divisor_ops() {
+        fn check<T>(n: T, d: T)
+        where
+            T: FastDiv + PartialEq + core::fmt::Debug,
+            T: core::ops::Div<Output = T> + core::ops::Rem<Output = T>,
+            T: core::ops::Div<Divisor<T>, Output = T> + core::ops::Rem<Divisor<T>, Output = T>,
+            T: core::ops::DivAssign<Divisor<T>> + core::ops::RemAssign<Divisor<T>>,
+        {
+            let divisor = Divisor::new(d);
+            assert_eq!(n / divisor, n / d);
+            assert_eq!(n % divisor, n % d);
+
+            let mut q = n;
+            q /= divisor;
+            assert_eq!(q, n / d);
+            let mut r = n;
+            r %= divisor;
+            assert_eq!(r, n % d);
+        }
+
+        for d in 1..100_i32 {
+            for n in -100..100_i32 {
+                check(n, d);
+                check(n, -d);
+                check(n as i64, d as i64);
+                check(n as u8, d as u8);
+                check(n as u32, d as u32);
+                check(n as u64, d as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn const_api() {
+        const P: PrecomputedDivU64 = PrecomputedDivU64::new(10);
+        const Q: u64 = P.div(1234);
+        const R: u64 = P.rem(1234, 10);
+        const MULTIPLE: bool = P.is_multiple_of(1230);
+        assert_eq!((Q, R, MULTIPLE), (123, 4, true));
+
+        const S: PrecomputedDivI32 = PrecomputedDivI32::new(-10);
+        const QR: (i32, i32) = S.div_rem(-1234, -10);
+        assert_eq!(QR, (123, -4));
+
+        const TABLE: [PrecomputedDivU16; 3] = {
+            let mut table = [PrecomputedDivU16::new(1); 3];
+            let mut i = 0;
+            while i < table.len() {
+                table[i] = PrecomputedDivU16::new(i as u16 + 1);
+                i += 1;
+            }
+            table
+        };
+        for (i, p) in TABLE.iter().enumerate() {
+            assert!(*p == (i as u16 + 1).precompute_div());
+        }
+
+        assert_eq!(PrecomputedDivU128::try_new(0), Err(DivisorError::Zero));
+    }
+
+    #[test]
+    fn recover_divisor() {
+        macro_rules! check {
+            ($($d: expr),*) => {$(
+                assert_eq!($d.precompute_div().divisor(), $d);
+            )*};
+        }
+
+        for d in (1..1000).chain([1 << 31, u32::MAX - 1, u32::MAX]) {
+            let (d8, d16) = ((d as u8).max(1), (d as u16).max(1));
+            check!(d, d8, d16, d as u64 * 0x1_0000_0001, (d as u128) << 96);
+            check!(d as i32, -(d as i64));
+        }
+        check!(u128::MAX, u128::MAX - 1, 3_u128, 1_u128);
+        check!(i32::MIN, i64::MIN);
+
+        let mut rng = xorshift();
+        for shift in 0..128 {
+            let d = ((rng() as u128) << 64 | rng() as u128) >> shift;
+            check!(d.max(1), (d >> 1) + 1, 1_u128 << (127 - shift));
+        }
+    }
+
+    #[test]
+    fn precomputed_traits() {
+        use std::collections::HashSet;
+
+        let p = 7_u32.precompute_div();
+        assert_eq!(format!("{:?}", p), "PrecomputedDivU32 { divisor: 7 }");
+        assert_eq!(
+            format!("{:?}", (-3_i64).precompute_div()),
+            "PrecomputedDivI64 { divisor: -3 }"
+        );
+        assert_eq!(p.to_string(), "7");
+        assert_eq!(format!("{:>4}", 5_u8.precompute_div()), "   5");
+
+        let mut unsigned: Vec<_> = [9_u64, 1, u64::MAX, 2, 1 << 40]
+            .map(PrecomputedDivU64::new)
+            .to_vec();
+        unsigned.sort();
+        let sorted: Vec<_> = unsigned.iter().map(|p| p.divisor()).collect();
+        assert_eq!(sorted, [1, 2, 9, 1 << 40, u64::MAX]);
+
+        let mut signed: Vec<_> = [3_i32, -1, i32::MIN, i32::MAX, 1, -7]
+            .map(PrecomputedDivI32::new)
+            .to_vec();
+        signed.sort();
+        let sorted: Vec<_> = signed.iter().map(|p| p.divisor()).collect();
+        assert_eq!(sorted, [i32::MIN, -7, -1, 1, 3, i32::MAX]);
+
+        let mut wide: Vec<_> = [u128::MAX, 1 << 64, 7, (1 << 64) - 1, 1, (1 << 64) + 1]
+            .map(PrecomputedDivU128::new)
+            .to_vec();
+        wide.sort();
+        let sorted: Vec<_> = wide.iter().map(|p| p.divisor()).collect();
+        assert_eq!(
+            sorted,
+            [1, 7, (1 << 64) - 1, 1 << 64, (1 << 64) + 1, u128::MAX]
+        );
+
+        let mut rng = xorshift();
+        for _ in 0..1000 {
+            let (a, b) = (rng() as i64 >> (rng() % 64), rng() as i64 >> (rng() % 64));
+            if a != 0 && b != 0 {
+                let (pa, pb) = (PrecomputedDivI64::new(a), PrecomputedDivI64::new(b));
+                assert_eq!(pa.cmp(&pb), a.cmp(&b));
+                let (a, b) = (a as u64, b as u64);
+                let (pa, pb) = (PrecomputedDivU64::new(a), PrecomputedDivU64::new(b));
+                assert_eq!(pa.cmp(&pb), a.cmp(&b));
+            }
+        }
+
+        let set: HashSet<_> = [3_u16, 5, 3]
+            .map(PrecomputedDivU16::new)
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&PrecomputedDivU16::new(5)));
+    }
+
+    #[test]
+    fn precomputed_divisor() {
+        type Expected<T> = (T, T, bool);
+        fn check<D: PrecomputedDivisor>(d: &D, values: &[D::Int], expected: &[Expected<D::Int>])
+        where
+            D::Int: PartialEq + core::fmt::Debug,
+        {
+            for (&n, &expected) in values.iter().zip(expected) {
+                assert_eq!((d.div(n), d.rem(n), d.is_multiple(n)), expected);
+            }
+        }
+
+        for j in 1..300_u32 {
+            let values: Vec<u32> = (0..300).chain([u32::MAX]).collect();
+            let expected: Vec<_> = values.iter().map(|&i| (i / j, i % j, i % j == 0)).collect();
+            check(&PrecomputedDivU32::new(j), &values, &expected);
+            check(&Divisor::new(j), &values, &expected);
+            if j > 1 {
+                check(&BranchFreeDivU32::new(j), &values, &expected);
+            }
+            assert_eq!(PrecomputedDivisor::divisor(&Divisor::new(j)), j);
+
+            let j = -(j as i64);
+            let values: Vec<i64> = (-300..300).chain([i64::MIN, i64::MAX]).collect();
+            let expected: Vec<_> = values
+                .iter()
+                .map(|&i| (i.wrapping_div(j), i.wrapping_rem(j), i.wrapping_rem(j) == 0))
+                .collect();
+            check(&PrecomputedDivI64::new(j), &values, &expected);
+            check(&Divisor::new(j), &values, &expected);
+            assert_eq!(PrecomputedDivisor::divisor(&PrecomputedDivI64::new(j)), j);
+        }
+
+        let values: Vec<u64> = (0..300).chain([u64::MAX - 1, u64::MAX]).collect();
+        for j in [1, 2, 7, 1 << 40, u64::MAX] {
+            let expected: Vec<_> = values.iter().map(|&i| (i / j, i % j, i % j == 0)).collect();
+            check(&PrecomputedDivU64::new(j), &values, &expected);
+            check(&MagicDivU64::new(j), &values, &expected);
+            if j > 1 {
+                check(&BranchFreeDivU64::new(j), &values, &expected);
+            }
+        }
+
+        let values = [0, 1, u128::MAX - 1, u128::MAX];
+        let expected = values.map(|i| (i / 3, i % 3, i % 3 == 0));
+        check(&PrecomputedDivU128::new(3), &values, &expected);
+        check(&Divisor::new(3_u128), &values, &expected);
+
+        let values = [i32::MIN, -1, 0, 1, i32::MAX];
+        let expected = values.map(|i| (i.wrapping_div(-1), i.wrapping_rem(-1), true));
+        check(&PrecomputedDivI32::new(-1), &values, &expected);
+        let expected = values.map(|i| (i / i32::MIN, i % i32::MIN, i % i32::MIN == 0));
+        check(&PrecomputedDivI32::new(i32::MIN), &values, &expected);
+    }
+
+    #[test]
+    fn const_div() {
+        fn check<D, P>(d: D, p: P, values: impl Iterator<Item = D::Int>)
+        where
+            D: PrecomputedDivisor,
+            P: PrecomputedDivisor<Int = D::Int>,
+            D::Int: PartialEq + core::fmt::Debug,
+        {
+            assert_eq!(d.divisor(), p.divisor());
+            for n in values {
+                assert_eq!((d.div(n), d.rem(n)), (p.div(n), p.rem(n)));
+                assert_eq!(d.is_multiple(n), p.is_multiple(n));
+            }
+        }
+
+        assert_eq!(core::mem::size_of::<ConstDiv<7, u128>>(), 0);
+        check(ConstDiv::<7, u8>::new(), Divisor::new(7_u8), 0..=u8::MAX);
+        check(
+            ConstDiv::<255, u8>::new(),
+            Divisor::new(255_u8),
+            0..=u8::MAX,
+        );
+        check(
+            ConstDiv::<1000, u16>::new(),
+            Divisor::new(1000_u16),
+            0..=u16::MAX,
+        );
+        check(
+            ConstDiv::<3, u32>::new(),
+            Divisor::new(3_u32),
+            (0..1000).chain([u32::MAX]),
+        );
+        check(
+            ConstDiv::<{ 1 << 40 }>::new(),
+            Divisor::new(1_u64 << 40),
+            (0..1000).map(|i| i << 32),
+        );
+        check(
+            ConstDiv::<10, u128>::new(),
+            Divisor::new(10_u128),
+            (0..1000).chain([u128::MAX]),
+        );
+        check(ConstDiv::<7, i32>::new(), Divisor::new(7_i32), -1000..1000);
+        check(
+            ConstDiv::<{ i64::MAX as u64 }, i64>::new(),
+            Divisor::new(i64::MAX),
+            [i64::MIN, -1, 0, i64::MAX].into_iter(),
+        );
+
+        const D: ConstDiv<10, u32> = ConstDiv::new();
+        const QR: (u32, u32) = D.div_rem(1234);
+        assert_eq!(QR, (123, 4));
+        assert!(D.is_multiple_of(120));
+    }
+
+    #[test]
+    fn div_ceil_round() {
+        fn round_ref(n: i128, d: i128, half_even: bool) -> i128 {
+            let (q, r) = (n / d, n % d);
+            let step = if (n < 0) != (d < 0) { -1 } else { 1 };
+            let (r, d) = (r.abs(), d.abs());
+            let away = 2 * r > d || (2 * r == d && (!half_even || q % 2 != 0));
+            q + step * away as i128
+        }
+        fn ceil_ref(n: i128, d: i128) -> i128 {
+            let q = n / d;
+            q + (n % d != 0 && (n < 0) == (d < 0)) as i128
+        }
+
+        let n = 300;
+        for j in (1..n).chain([u32::MAX - 1, u32::MAX]) {
+            let p = j.precompute_div();
+            for i in (0..n).chain([u32::MAX - 1, u32::MAX]) {
+                let (i_, j_) = (i as i128, j as i128);
+                assert_eq!(i.fast_div_ceil(p), i.div_ceil(j));
+                assert_eq!(i.fast_div_round(p, j) as i128, round_ref(i_, j_, false));
+                assert_eq!(
+                    i.fast_div_round_half_even(p, j) as i128,
+                    round_ref(i_, j_, true)
+                );
+            }
+        }
+        for j in (1..n as u64).chain([u64::MAX - 1, u64::MAX]) {
+            let p = j.precompute_div();
+            for i in (0..n as u64).chain([u64::MAX - 1, u64::MAX]) {
+                let (i_, j_) = (i as i128, j as i128);
+                assert_eq!(i.fast_div_ceil(p), i.div_ceil(j));
+                assert_eq!(i.fast_div_round(p, j) as i128, round_ref(i_, j_, false));
+                assert_eq!(
+                    i.fast_div_round_half_even(p, j) as i128,
+                    round_ref(i_, j_, true)
+                );
+            }
+        }
+
+        let extremes = [i64::MIN + 1, i64::MIN + 2, i64::MAX - 1, i64::MAX];
+        for j in (-300..300_i64).chain(extremes).filter(|&j| j != 0) {
+            let p = j.precompute_div();
+            for i in (-300..300).chain(extremes) {
+                let (i_, j_) = (i as i128, j as i128);
+                assert_eq!(i.fast_div_ceil(p) as i128, ceil_ref(i_, j_));
+                assert_eq!(i.fast_div_round(p, j) as i128, round_ref(i_, j_, false));
+                assert_eq!(
+                    i.fast_div_round_half_even(p, j) as i128,
+                    round_ref(i_, j_, true)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn magic_div_u64() {
+        let n: u64 = 1000;
+        for j in 1..n {
+            let p = MagicDivU64::new(j);
+            for i in (0..n).chain([u64::MAX - 1, u64::MAX]) {
+                assert_eq!(p.div(i), i / j);
+                assert_eq!(p.rem(i, j), i % j);
+            }
+        }
+
+        let mut next = xorshift();
+        for k in 0..64 {
+            let pow = 1_u64 << k;
+            for j in [pow - 1, pow, pow + 1, next() >> k] {
+                let j = j.max(1);
+                let p = MagicDivU64::new(j);
+                for i in [0, 1, j - 1, j, u64::MAX - 1, u64::MAX, next(), next()] {
+                    assert_eq!(p.div_rem(i, j), (i / j, i % j));
+                    assert_eq!(p.is_multiple_of(i), i % j == 0);
+                }
+                assert_eq!(p.divisor(), j);
+                assert_eq!(p, MagicDivU64::from(NonZeroU64::new(j).unwrap()));
+            }
+        }
+        assert_eq!(MagicDivU64::try_new(0), Err(DivisorError::Zero));
+    }
+
+    #[test]
+    fn branchfree_div() {
+        let n: u32 = 1000;
+        for j in (2..n).chain([u32::MAX - 1, u32::MAX]) {
+            let p = BranchFreeDivU32::new(j);
+            assert_eq!(p.divisor(), j);
+            for i in (0..n).chain([u32::MAX - 1, u32::MAX]) {
+                assert_eq!(p.div_rem(i, j), (i / j, i % j));
+                assert_eq!(p.is_multiple_of(i), i % j == 0);
+            }
+        }
+        let n: u64 = 1000;
+        for j in (2..n).chain([u64::MAX - 1, u64::MAX]) {
+            let p = BranchFreeDivU64::new(j);
+            assert_eq!(p.divisor(), j);
+            for i in (0..n).chain([u64::MAX - 1, u64::MAX]) {
+                assert_eq!(p.div_rem(i, j), (i / j, i % j));
+                assert_eq!(p.is_multiple_of(i), i % j == 0);
+            }
+        }
+
+        let mut next = xorshift();
+        for k in 1..64 {
+            let pow = 1_u64 << k;
+            for j in [pow - 1, pow, pow + 1, next() >> k] {
+                let j = j.max(2);
+                let p = BranchFreeDivU64::new(j);
+                let p32 = BranchFreeDivU32::new(j as u32 | 2);
+                for i in [0, 1, j - 1, j, u64::MAX, next(), next()] {
+                    assert_eq!(p.div_rem(i, j), (i / j, i % j));
+                    assert_eq!(p32.div(i as u32), i as u32 / (j as u32 | 2));
+                }
+            }
+        }
+
+        assert_eq!(BranchFreeDivU32::try_new(0), Err(DivisorError::Zero));
+        assert_eq!(BranchFreeDivU64::try_new(1), Err(DivisorError::Unsupported));
+        assert_eq!(
+            BranchFreeDivU32::try_from(NonZeroU32::new(7).unwrap()),
+            Ok(BranchFreeDivU32::new(7))
+        );
+        assert_eq!(
+            BranchFreeDivU64::try_from(NonZeroU64::MIN),
+            Err(DivisorError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn div_pow2() {
+        for k in 0..32 {
+            let j = 1_u32 << k;
+            let p = j.precompute_div();
+            for i in (0..1000).chain([j - 1, j, j + 1, u32::MAX - 1, u32::MAX]) {
+                assert_eq!(i.fast_div_rem(p, j), (i / j, i % j));
+                assert_eq!(FastDiv::is_multiple_of(i, p), i % j == 0);
+            }
+            // their neighbours must keep using the factor
+            for j in [j.wrapping_sub(1), j + 1].into_iter().filter(|&j| j != 0) {
+                let p = j.precompute_div();
+                for i in (0..1000).chain([u32::MAX - 1, u32::MAX]) {
+                    assert_eq!(i.fast_div_rem(p, j), (i / j, i % j));
+                    assert_eq!(FastDiv::is_multiple_of(i, p), i % j == 0);
+                }
+            }
+        }
+        for k in 0..64 {
+            let j = 1_u64 << k;
+            let p = j.precompute_div();
+            for i in (0..1000).chain([j - 1, j, j + 1, u64::MAX - 1, u64::MAX]) {
+                assert_eq!(i.fast_div_rem(p, j), (i / j, i % j));
+                assert_eq!(FastDiv::is_multiple_of(i, p), i % j == 0);
+            }
+            for j in [j.wrapping_sub(1), j + 1].into_iter().filter(|&j| j != 0) {
+                let p = j.precompute_div();
+                for i in (0..1000).chain([u64::MAX - 1, u64::MAX]) {
+                    assert_eq!(i.fast_div_rem(p, j), (i / j, i % j));
+                    assert_eq!(FastDiv::is_multiple_of(i, p), i % j == 0);
+                }
+            }
+        }
+        for k in 0..63 {
+            let j = -(1_i64 << k);
+            let p = j.precompute_div();
+            for i in (-1000..1000).chain([i64::MIN + 1, i64::MAX]) {
+                assert_eq!(i.fast_div_rem(p, j), (i / j, i % j));
+                assert_eq!(i.is_multiple_of(p), i % j == 0);
+            }
+        }
+    }
+
+    #[test]
+    fn exact_div() {
+        for j in (1..1000_u32).chain([u32::MAX - 1, u32::MAX]) {
+            let p = PrecomputedExactDivU32::new(j);
+            for q in (0..1000)
+                .chain([u32::MAX / j])
+                .filter(|&q| q <= u32::MAX / j)
+            {
+                assert_eq!(p.div_exact(q * j), q);
+            }
+        }
+        for j in (1..1000_u64).chain([1 << 40, 3 << 40, u64::MAX - 1, u64::MAX]) {
+            let p = PrecomputedExactDivU64::new(j);
+            for q in (0..1000)
+                .chain([u64::MAX / j])
+                .filter(|&q| q <= u64::MAX / j)
+            {
+                assert_eq!(p.div_exact(q * j), q);
+            }
+        }
+        assert_eq!(PrecomputedExactDivU64::try_new(0), Err(DivisorError::Zero));
+    }
+
+    #[test]
+    fn divisibility_u64() {
+        let extremes = [u64::MAX - 1, u64::MAX, 1 << 63, (1 << 63) + 1];
+        for j in (1..1000_u64).chain(extremes).chain([3 << 40, 5 << 62]) {
+            let p = PrecomputedDivisibilityU64::new(j);
+            for i in (0..3000).chain(extremes) {
+                assert_eq!(p.is_multiple_of(i), i % j == 0);
+            }
+        }
+        assert_eq!(
+            PrecomputedDivisibilityU64::try_new(0),
+            Err(DivisorError::Zero)
+        );
+    }
+
+    #[test]
+    fn slices() {
+        fn check<T: FastDiv + PartialEq + core::fmt::Debug>(values: &[T], d: T) {
+            let p = d.precompute_div();
+
+            let mut quotients = values.to_vec();
+            T::fast_div_slice(&mut quotients, p);
+            let expected: Vec<T> = values.iter().map(|v| v.fast_div(p)).collect();
+            assert_eq!(quotients, expected);
+
+            let mut dst = values.to_vec();
+            T::fast_div_slice_into(values, &mut dst, p);
+            assert_eq!(dst, expected);
+
+            let mut remainders = values.to_vec();
+            T::fast_mod_slice(&mut remainders, p, d);
+            let expected: Vec<T> = values.iter().map(|v| v.fast_mod(p, d)).collect();
+            assert_eq!(remainders, expected);
+
+            let expected = values.iter().filter(|v| v.is_multiple_of(p)).count();
+            assert_eq!(T::count_multiples(values, p), expected);
+        }
+
+        let values: Vec<u32> = (0..2000).chain([u32::MAX - 1, u32::MAX]).collect();
+        for d in (1..100).chain([1 << 20, u32::MAX]) {
+            check(&values, d);
+            check(
+                &values.iter().map(|&v| v as u8).collect::<Vec<_>>(),
+                d as u8 | 1,
+            );
+            check(
+                &values.iter().map(|&v| v as u64 * 12345).collect::<Vec<_>>(),
+                d as u64,
+            );
+            check(
+                &values.iter().map(|&v| v as i32).collect::<Vec<_>>(),
+                -(d as i32),
+            );
+            check(
+                &values
+                    .iter()
+                    .map(|&v| v as usize * 12345)
+                    .collect::<Vec<_>>(),
+                d as usize,
+            );
+            check(
+                &values.iter().map(|&v| v as isize).collect::<Vec<_>>(),
+                -(d as isize),
+            );
         }
+        assert_eq!(u32::count_multiples(&values, 3_u32.precompute_div()), 668);
     }
 }
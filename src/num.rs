@@ -0,0 +1,85 @@
+//! Integration with `num-traits`, for generic numeric code.
+
+use crate::FastDiv;
+use num_traits::PrimInt;
+
+/// A primitive integer that supports fast division, for code that is generic over
+/// [`PrimInt`](num_traits::PrimInt).
+///
+/// This is implemented for every type that implements both traits, which covers every
+/// supported width.
+///
+/// # Example
+/// ```
+/// use fastdiv::{FastDiv, FastDivPrimInt};
+///
+/// fn digit_sum<T: FastDivPrimInt>(mut n: T) -> T {
+///     let ten = T::from(10).unwrap();
+///     let p = ten.precompute_div();
+///     let mut sum = T::zero();
+///     while n > T::zero() {
+///         let (q, r) = n.fast_div_rem(p, ten);
+///         sum = sum + r;
+///         n = q;
+///     }
+///     sum
+/// }
+///
+/// assert_eq!(digit_sum(1234_u16), 10);
+/// assert_eq!(digit_sum(u128::MAX), 165);
+/// ```
+pub trait FastDivPrimInt: PrimInt + FastDiv {}
+
+impl<T: PrimInt + FastDiv> FastDivPrimInt for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_integer::Integer;
+
+    // `Integer::is_multiple_of` would be ambiguous with `FastDiv::is_multiple_of` here
+    fn check<T: FastDivPrimInt + Integer + core::fmt::Debug>(values: &[T], divisors: &[T]) {
+        for &d in divisors {
+            let p = d.precompute_div();
+            for &n in values {
+                assert_eq!(n.fast_div_floor(p), Integer::div_floor(&n, &d));
+                assert_eq!(n.fast_mod_floor(p, d), Integer::mod_floor(&n, &d));
+                assert_eq!(n.fast_div_rem(p, d), Integer::div_rem(&n, &d));
+                assert_eq!(
+                    FastDiv::is_multiple_of(n, p),
+                    Integer::is_multiple_of(&n, &d)
+                );
+            }
+        }
+    }
+
+    fn check_all<T: FastDivPrimInt + Integer + core::fmt::Debug>() {
+        let mut values: Vec<T> = (0..200).filter_map(T::from).collect();
+        values.extend([T::max_value(), T::max_value() - T::one()]);
+        if T::min_value() < T::zero() {
+            values.extend((1..200).filter_map(|i: i32| T::from(-i)));
+            values.extend([
+                T::min_value() + T::one(),
+                T::min_value() + T::from(7).unwrap(),
+            ]);
+        }
+        let mut divisors: Vec<T> = values.iter().copied().filter(|&d| d != T::zero()).collect();
+        if T::min_value() < T::zero() {
+            divisors.push(T::min_value());
+        }
+        check(&values, &divisors);
+    }
+
+    #[test]
+    fn prim_int() {
+        check_all::<u8>();
+        check_all::<u16>();
+        check_all::<u32>();
+        check_all::<u64>();
+        check_all::<u128>();
+        check_all::<usize>();
+        check_all::<i32>();
+        check_all::<i64>();
+        check_all::<isize>();
+    }
+}
@@ -0,0 +1,121 @@
+//! Serde support for the precomputed division factors.
+//!
+//! The divisor is serialized rather than the factor, so that the serialized form does not depend
+//! on the internal representation. Deserialization recomputes the factor, and fails if the
+//! divisor is rejected by the constructor.
+
+use crate::{
+    Divisor, FastDiv, PrecomputedDivI32, PrecomputedDivI64, PrecomputedDivU128, PrecomputedDivU16,
+    PrecomputedDivU32, PrecomputedDivU64, PrecomputedDivU8,
+};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! impl_serde {
+    ($ty: ty, $precomputed: ty) => {
+        impl Serialize for $precomputed {
+            #[inline]
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.divisor().serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $precomputed {
+            #[inline]
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                <$precomputed>::try_new(<$ty>::deserialize(deserializer)?).map_err(D::Error::custom)
+            }
+        }
+    };
+}
+
+impl_serde!(u8, PrecomputedDivU8);
+impl_serde!(u16, PrecomputedDivU16);
+impl_serde!(u32, PrecomputedDivU32);
+impl_serde!(u64, PrecomputedDivU64);
+impl_serde!(u128, PrecomputedDivU128);
+impl_serde!(i32, PrecomputedDivI32);
+impl_serde!(i64, PrecomputedDivI64);
+
+impl<T: FastDiv + Serialize> Serialize for Divisor<T> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.divisor().serialize(serializer)
+    }
+}
+
+impl<'de, T: FastDiv + Deserialize<'de>> Deserialize<'de> for Divisor<T> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Divisor::try_new(T::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! check {
+        ($ty: ty, $precomputed: ty, $divisors: expr) => {
+            for d in $divisors {
+                let d: $ty = d;
+                let json = serde_json::to_string(&<$precomputed>::new(d)).unwrap();
+                assert_eq!(json, serde_json::to_string(&d).unwrap());
+                let p: $precomputed = serde_json::from_str(&json).unwrap();
+                assert!(p == <$precomputed>::new(d));
+            }
+            let err = serde_json::from_str::<$precomputed>("0").err().unwrap();
+            assert_eq!(err.to_string(), "divisor is zero");
+        };
+    }
+
+    #[test]
+    fn serde_precomputed() {
+        check!(u8, PrecomputedDivU8, 1..=u8::MAX);
+        check!(u16, PrecomputedDivU16, (1..=u16::MAX).step_by(7));
+        check!(
+            u32,
+            PrecomputedDivU32,
+            [1, 2, 3, 7, 1 << 31, u32::MAX - 1, u32::MAX]
+        );
+        check!(
+            u64,
+            PrecomputedDivU64,
+            [1, 2, 3, 7, 1 << 32, 1 << 63, u64::MAX - 1, u64::MAX]
+        );
+        check!(
+            u128,
+            PrecomputedDivU128,
+            [
+                1,
+                2,
+                3,
+                7,
+                1 << 64,
+                (1 << 64) + 1,
+                1 << 127,
+                u128::MAX - 1,
+                u128::MAX
+            ]
+        );
+        check!(
+            i32,
+            PrecomputedDivI32,
+            [1, -1, 3, -7, i32::MIN, i32::MIN + 1, i32::MAX]
+        );
+        check!(
+            i64,
+            PrecomputedDivI64,
+            [1, -1, 3, -7, i64::MIN, i64::MIN + 1, i64::MAX]
+        );
+    }
+
+    #[test]
+    fn serde_divisor() {
+        let d = Divisor::new(-7_i64);
+        let json = serde_json::to_string(&d).unwrap();
+        assert_eq!(json, "-7");
+        assert_eq!(serde_json::from_str::<Divisor<i64>>(&json).unwrap(), d);
+        assert!(serde_json::from_str::<Divisor<u32>>("0").is_err());
+    }
+}
@@ -0,0 +1,399 @@
+//! Lane-wise fast division of `core::simd` vectors.
+//!
+//! Each lane has its own divisor. To divide every lane by the same divisor, use the `*_simd`
+//! methods of the scalar precomputed division factor, or convert it with [`From`]. Both broadcast
+//! the factor across lanes.
+
+use crate::{
+    compute_m_u32, compute_m_u64, DivisorError, FastDiv, PrecomputedDivU32, PrecomputedDivU64,
+};
+use core::simd::cmp::{SimdPartialEq, SimdPartialOrd};
+use core::simd::num::{SimdInt, SimdUint};
+use core::simd::{Mask, Simd};
+
+/// Precomputed division factors for a vector of `u32` divisors, one per lane.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct PrecomputedDivSimdU32<const N: usize> {
+    m: Simd<u64, N>,
+}
+
+/// Precomputed division factors for a vector of `u64` divisors, one per lane.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct PrecomputedDivSimdU64<const N: usize> {
+    // low and high halves of the 128-bit factors
+    m: [Simd<u64, N>; 2],
+}
+
+// `1` in the lanes where `mask` is set, `0` elsewhere
+#[inline]
+fn ones_u32<const N: usize>(mask: Mask<i32, N>) -> Simd<u32, N> {
+    (-mask.to_simd()).cast()
+}
+#[inline]
+fn ones_u64<const N: usize>(mask: Mask<i64, N>) -> Simd<u64, N> {
+    (-mask.to_simd()).cast()
+}
+
+// full 64x64 -> 128 multiplication, returned as (low, high)
+#[inline]
+fn widening_mul_u64<const N: usize>(a: Simd<u64, N>, b: Simd<u64, N>) -> [Simd<u64, N>; 2] {
+    let low_halves = Simd::splat(0xFFFFFFFF);
+    let (a_lo, a_hi) = (a & low_halves, a >> 32);
+    let (b_lo, b_hi) = (b & low_halves, b >> 32);
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+    let cross = (lo_lo >> 32) + (hi_lo & low_halves) + lo_hi;
+    let low = (cross << 32) | (lo_lo & low_halves);
+    let high = hi_hi + (hi_lo >> 32) + (cross >> 32);
+    [low, high]
+}
+
+impl<const N: usize> PrecomputedDivSimdU32<N> {
+    /// Precompute the division factors for the divisors `d`.
+    ///
+    /// # Panics
+    /// Panics if any lane of `d` is zero.
+    #[inline]
+    pub fn new(d: Simd<u32, N>) -> Self {
+        Self::try_new(d).expect("divisor is zero")
+    }
+
+    /// Precompute the division factors for the divisors `d`, or return an error if any lane of
+    /// `d` is zero.
+    #[inline]
+    pub fn try_new(d: Simd<u32, N>) -> Result<Self, DivisorError> {
+        if d.simd_eq(Simd::splat(0)).any() {
+            return Err(DivisorError::Zero);
+        }
+        Ok(Self {
+            m: Simd::from_array(d.to_array().map(compute_m_u32)),
+        })
+    }
+
+    /// Broadcast the division factor of a single divisor across all lanes.
+    #[inline]
+    pub fn splat(precomputed: PrecomputedDivU32) -> Self {
+        Self {
+            m: Simd::splat(precomputed.m),
+        }
+    }
+
+    /// Divide each lane of `n` by its divisor.
+    #[inline]
+    pub fn div(&self, n: Simd<u32, N>) -> Simd<u32, N> {
+        let (m_lo, m_hi) = (self.m & Simd::splat(0xFFFFFFFF), self.m >> 32);
+        let a = n.cast::<u64>();
+        let bottom_half = (m_lo * a + a) >> 32;
+        ((m_hi * a + bottom_half) >> 32).cast()
+    }
+
+    /// Compute the remainder of the division of each lane of `n` by its divisor in `d`.
+    /// If `self` does not come from the same provided divisors, the result is unspecified.
+    #[inline]
+    pub fn rem(&self, n: Simd<u32, N>, d: Simd<u32, N>) -> Simd<u32, N> {
+        self.div_rem(n, d).1
+    }
+
+    /// Compute both the quotients and the remainders of the division of each lane of `n` by its
+    /// divisor in `d`.
+    /// If `self` does not come from the same provided divisors, the result is unspecified.
+    #[inline]
+    pub fn div_rem(&self, n: Simd<u32, N>, d: Simd<u32, N>) -> (Simd<u32, N>, Simd<u32, N>) {
+        let q = self.div(n);
+        (q, n - q * d)
+    }
+
+    /// Check which lanes of `n` are multiples of their divisor.
+    #[inline]
+    pub fn is_multiple_of(&self, n: Simd<u32, N>) -> Mask<i32, N> {
+        let a = n.cast::<u64>();
+        (self.m * a + a).simd_le(self.m).cast()
+    }
+}
+
+impl<const N: usize> PrecomputedDivSimdU64<N> {
+    /// Precompute the division factors for the divisors `d`.
+    ///
+    /// # Panics
+    /// Panics if any lane of `d` is zero.
+    #[inline]
+    pub fn new(d: Simd<u64, N>) -> Self {
+        Self::try_new(d).expect("divisor is zero")
+    }
+
+    /// Precompute the division factors for the divisors `d`, or return an error if any lane of
+    /// `d` is zero.
+    #[inline]
+    pub fn try_new(d: Simd<u64, N>) -> Result<Self, DivisorError> {
+        if d.simd_eq(Simd::splat(0)).any() {
+            return Err(DivisorError::Zero);
+        }
+        let m = d.to_array().map(compute_m_u64);
+        Ok(Self {
+            m: [
+                Simd::from_array(m.map(|m| m as u64)),
+                Simd::from_array(m.map(|m| (m >> 64) as u64)),
+            ],
+        })
+    }
+
+    /// Broadcast the division factor of a single divisor across all lanes.
+    #[inline]
+    pub fn splat(precomputed: PrecomputedDivU64) -> Self {
+        Self {
+            m: [
+                Simd::splat(precomputed.m as u64),
+                Simd::splat((precomputed.m >> 64) as u64),
+            ],
+        }
+    }
+
+    /// Divide each lane of `n` by its divisor.
+    #[inline]
+    pub fn div(&self, n: Simd<u64, N>) -> Simd<u64, N> {
+        let [bottom_lo, bottom_hi] = widening_mul_u64(self.m[0], n);
+        let bottom_half = bottom_hi + ones_u64((bottom_lo + n).simd_lt(n));
+        let [top_lo, top_hi] = widening_mul_u64(self.m[1], n);
+        top_hi + ones_u64((top_lo + bottom_half).simd_lt(top_lo))
+    }
+
+    /// Compute the remainder of the division of each lane of `n` by its divisor in `d`.
+    /// If `self` does not come from the same provided divisors, the result is unspecified.
+    #[inline]
+    pub fn rem(&self, n: Simd<u64, N>, d: Simd<u64, N>) -> Simd<u64, N> {
+        self.div_rem(n, d).1
+    }
+
+    /// Compute both the quotients and the remainders of the division of each lane of `n` by its
+    /// divisor in `d`.
+    /// If `self` does not come from the same provided divisors, the result is unspecified.
+    #[inline]
+    pub fn div_rem(&self, n: Simd<u64, N>, d: Simd<u64, N>) -> (Simd<u64, N>, Simd<u64, N>) {
+        let q = self.div(n);
+        (q, n - q * d)
+    }
+
+    /// Check which lanes of `n` are multiples of their divisor.
+    #[inline]
+    pub fn is_multiple_of(&self, n: Simd<u64, N>) -> Mask<i64, N> {
+        // `m * n + n`, truncated to 128 bits
+        let [lo, hi] = widening_mul_u64(self.m[0], n);
+        let lowbits_lo = lo + n;
+        let lowbits_hi = hi + self.m[1] * n + ones_u64(lowbits_lo.simd_lt(n));
+        lowbits_hi.simd_lt(self.m[1])
+            | (lowbits_hi.simd_eq(self.m[1]) & lowbits_lo.simd_le(self.m[0]))
+    }
+}
+
+impl<const N: usize> From<PrecomputedDivU32> for PrecomputedDivSimdU32<N> {
+    #[inline]
+    fn from(precomputed: PrecomputedDivU32) -> Self {
+        Self::splat(precomputed)
+    }
+}
+
+impl<const N: usize> From<PrecomputedDivU64> for PrecomputedDivSimdU64<N> {
+    #[inline]
+    fn from(precomputed: PrecomputedDivU64) -> Self {
+        Self::splat(precomputed)
+    }
+}
+
+macro_rules! impl_scalar_simd {
+    ($ty: ty, $scalar: ty, $precomputed: ident, $mask: ty) => {
+        /// Lane-wise operations on vectors, with the division factor broadcast across all lanes.
+        impl $scalar {
+            /// Divide each lane of `n` by the divisor.
+            #[inline]
+            pub fn div_simd<const N: usize>(&self, n: Simd<$ty, N>) -> Simd<$ty, N> {
+                $precomputed::splat(*self).div(n)
+            }
+
+            /// Compute the remainder of the division of each lane of `n` by the divisor `d`.
+            /// If the precomputed division factor does not come from the same provided divisor,
+            /// the result is unspecified.
+            #[inline]
+            pub fn rem_simd<const N: usize>(&self, n: Simd<$ty, N>, d: $ty) -> Simd<$ty, N> {
+                $precomputed::splat(*self).rem(n, Simd::splat(d))
+            }
+
+            /// Compute both the quotients and the remainders of the division of each lane of `n`
+            /// by the divisor `d`.
+            /// If the precomputed division factor does not come from the same provided divisor,
+            /// the result is unspecified.
+            #[inline]
+            pub fn div_rem_simd<const N: usize>(
+                &self,
+                n: Simd<$ty, N>,
+                d: $ty,
+            ) -> (Simd<$ty, N>, Simd<$ty, N>) {
+                $precomputed::splat(*self).div_rem(n, Simd::splat(d))
+            }
+
+            /// Check which lanes of `n` are multiples of the divisor.
+            #[inline]
+            pub fn is_multiple_of_simd<const N: usize>(&self, n: Simd<$ty, N>) -> Mask<$mask, N> {
+                $precomputed::splat(*self).is_multiple_of(n)
+            }
+        }
+    };
+}
+
+impl_scalar_simd!(u32, PrecomputedDivU32, PrecomputedDivSimdU32, i32);
+impl_scalar_simd!(u64, PrecomputedDivU64, PrecomputedDivSimdU64, i64);
+
+macro_rules! impl_fast_div_simd {
+    ($ty: ty, $precomputed: ident, $ones: ident) => {
+        /// Lane-wise division, where each lane has its own divisor.
+        ///
+        /// [`is_multiple_of`](FastDiv::is_multiple_of) is true when every lane is a multiple of
+        /// its divisor. The precomputed division factor has an inherent `is_multiple_of` method
+        /// that returns the lane-wise mask.
+        impl<const N: usize> FastDiv for Simd<$ty, N> {
+            type PrecomputedDiv = $precomputed<N>;
+
+            #[inline]
+            fn try_precompute_div(self) -> Result<Self::PrecomputedDiv, DivisorError> {
+                $precomputed::try_new(self)
+            }
+
+            #[inline]
+            fn fast_div(self, precomputed: Self::PrecomputedDiv) -> Self {
+                precomputed.div(self)
+            }
+
+            #[inline]
+            fn fast_mod(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                precomputed.rem(self, d)
+            }
+
+            #[inline]
+            fn is_multiple_of(self, precomputed: Self::PrecomputedDiv) -> bool {
+                precomputed.is_multiple_of(self).all()
+            }
+
+            #[inline]
+            fn fast_div_rem(self, precomputed: Self::PrecomputedDiv, d: Self) -> (Self, Self) {
+                precomputed.div_rem(self, d)
+            }
+
+            #[inline]
+            fn fast_div_ceil(self, precomputed: Self::PrecomputedDiv) -> Self {
+                precomputed.div(self) + $ones(!precomputed.is_multiple_of(self))
+            }
+
+            #[inline]
+            fn fast_div_round(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                let (q, r) = precomputed.div_rem(self, d);
+                // `2 * r >= d`, without overflowing
+                q + $ones(r.simd_ge(d - r))
+            }
+
+            #[inline]
+            fn fast_div_round_half_even(self, precomputed: Self::PrecomputedDiv, d: Self) -> Self {
+                let (q, r) = precomputed.div_rem(self, d);
+                let odd = (q & Simd::splat(1)).simd_eq(Simd::splat(1));
+                q + $ones(r.simd_gt(d - r) | (r.simd_eq(d - r) & odd))
+            }
+        }
+    };
+}
+
+impl_fast_div_simd!(u32, PrecomputedDivSimdU32, ones_u32);
+impl_fast_div_simd!(u64, PrecomputedDivSimdU64, ones_u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::xorshift;
+
+    macro_rules! check {
+        ($ty: ty, $divisors: expr, $values: expr) => {{
+            let divisors: Vec<$ty> = $divisors;
+            let values: Vec<$ty> = $values;
+            for d in divisors.chunks_exact(4) {
+                let d = Simd::<$ty, 4>::from_slice(d);
+                let p = d.precompute_div();
+                for n in values.chunks_exact(4) {
+                    let n = Simd::<$ty, 4>::from_slice(n);
+                    let (q, r) = n.fast_div_rem(p, d);
+                    assert_eq!(n.fast_div(p), n / d);
+                    assert_eq!(n.fast_mod(p, d), n % d);
+                    assert_eq!((q, r), (n / d, n % d));
+                    let multiples = p.is_multiple_of(n);
+                    for lane in 0..4 {
+                        assert_eq!(multiples.test(lane), n[lane] % d[lane] == 0);
+                        let ceil = n[lane] / d[lane] + (n[lane] % d[lane] != 0) as $ty;
+                        assert_eq!(n.fast_div_ceil(p)[lane], ceil);
+                    }
+                    assert_eq!(FastDiv::is_multiple_of(n, p), multiples.all());
+                }
+
+                // broadcast a scalar divisor
+                let scalar = d[0].precompute_div();
+                let p = Simd::<$ty, 4>::precompute_div(Simd::splat(d[0]));
+                assert!(p == scalar.into());
+                for n in values.chunks_exact(4) {
+                    let n = Simd::<$ty, 4>::from_slice(n);
+                    let (q, r) = (n.fast_div(p), n.fast_mod(p, Simd::splat(d[0])));
+                    assert_eq!(scalar.div_simd(n), q);
+                    assert_eq!(scalar.rem_simd(n, d[0]), r);
+                    assert_eq!(scalar.div_rem_simd(n, d[0]), (q, r));
+                    assert_eq!(scalar.is_multiple_of_simd(n), p.is_multiple_of(n));
+                }
+            }
+        }};
+    }
+
+    fn values<T: Copy>(special: &[T], mut next: impl FnMut() -> T) -> Vec<T> {
+        let mut values = special.to_vec();
+        values.extend((0..400).map(|_| next()));
+        values.truncate(values.len() / 4 * 4);
+        values
+    }
+
+    #[test]
+    fn div_simd_u32() {
+        let mut rng = xorshift();
+        let mut next = move || (rng() >> 32) as u32;
+        let special = [0, 1, 2, 3, 4, 7, 8, 1 << 31, u32::MAX - 1, u32::MAX];
+        let divisors: Vec<u32> = (1..100)
+            .chain((0..32).map(|k| 1 << k))
+            .chain(special[1..].iter().copied())
+            .chain((0..200).map(|_| next() >> (next() % 32)))
+            .filter(|&d| d != 0)
+            .collect();
+        let divisors = divisors[..divisors.len() / 4 * 4].to_vec();
+        check!(u32, divisors, values(&special, next));
+    }
+
+    #[test]
+    fn div_simd_u64() {
+        let mut next = xorshift();
+        let special = [0, 1, 2, 3, 4, 7, 8, 1 << 63, u64::MAX - 1, u64::MAX];
+        let divisors: Vec<u64> = (1..100)
+            .chain((0..64).map(|k| 1 << k))
+            .chain(special[1..].iter().copied())
+            .chain((0..200).map(|_| next() >> (next() % 64)))
+            .filter(|&d| d != 0)
+            .collect();
+        let divisors = divisors[..divisors.len() / 4 * 4].to_vec();
+        check!(u64, divisors, values(&special, next));
+    }
+
+    #[test]
+    fn try_new_simd() {
+        assert_eq!(
+            PrecomputedDivSimdU32::try_new(Simd::from_array([1, 2, 0, 4])).err(),
+            Some(DivisorError::Zero)
+        );
+        assert_eq!(
+            Simd::<u64, 2>::from_array([3, 0])
+                .try_precompute_div()
+                .err(),
+            Some(DivisorError::Zero)
+        );
+    }
+}
@@ -0,0 +1,293 @@
+//! Explicit AVX2 and AVX-512 kernels for batched `u32` and `u64` division and modulo. The AVX-512
+//! kernel needs Rust 1.89, so it is only compiled with the `avx512` feature.
+//!
+//! The `u32` kernels split the 64-bit factor `m` into two 32-bit halves, so that the 96-bit
+//! product of the scalar code turns into two 32x32 -> 64 bit multiplications per lane, which map
+//! directly to `vpmuludq`. Even and odd lanes are processed separately and blended back together.
+//!
+//! The `u64` kernel splits the 128-bit factor into four 32-bit limbs the same way, and leaves the
+//! loop to the auto-vectorizer, compiled for AVX2.
+
+use crate::{PrecomputedDivU32, PrecomputedDivU64, SimdKernels};
+use core::arch::x86_64::*;
+
+impl SimdKernels for u32 {
+    #[inline]
+    unsafe fn simd_div(
+        src: *const u32,
+        dst: *mut u32,
+        len: usize,
+        precomputed: PrecomputedDivU32,
+        d: Option<u32>,
+    ) -> usize {
+        #[cfg(feature = "avx512")]
+        if is_x86_feature_detected!("avx512f") {
+            return match d {
+                None => div_avx512::<false>(src, dst, len, precomputed.m, 0),
+                Some(d) => div_avx512::<true>(src, dst, len, precomputed.m, d),
+            };
+        }
+        if is_x86_feature_detected!("avx2") {
+            match d {
+                None => div_avx2::<false>(src, dst, len, precomputed.m, 0),
+                Some(d) => div_avx2::<true>(src, dst, len, precomputed.m, d),
+            }
+        } else {
+            0
+        }
+    }
+}
+
+impl SimdKernels for u64 {
+    #[inline]
+    unsafe fn simd_div(
+        src: *const u64,
+        dst: *mut u64,
+        len: usize,
+        precomputed: PrecomputedDivU64,
+        d: Option<u64>,
+    ) -> usize {
+        if is_x86_feature_detected!("avx2") {
+            match d {
+                None => div_u64_avx2::<false>(src, dst, len, precomputed.m, 0),
+                Some(d) => div_u64_avx2::<true>(src, dst, len, precomputed.m, d),
+            }
+        } else {
+            0
+        }
+    }
+}
+
+// `fastdiv_u64` as a schoolbook multiplication of 32-bit limbs, since there is no vector
+// 64x64 -> 128 bit multiplication. every step is at most `(2^32 - 1)^2 + 2 * (2^32 - 1)`, so the
+// sums never overflow, and the last one is the quotient
+#[inline]
+fn fastdiv_u64_limbs(a: u64, m: u128) -> u64 {
+    const LO: u64 = 0xFFFFFFFF;
+    let (m0, m1) = (m as u64 & LO, (m >> 32) as u64 & LO);
+    let (m2, m3) = ((m >> 64) as u64 & LO, (m >> 96) as u64);
+    let (a0, a1) = (a & LO, a >> 32);
+    // `m * a0 + a`
+    let t = m0 * a0 + a0;
+    let t = m1 * a0 + a1 + (t >> 32);
+    let r1 = t & LO;
+    let t = m2 * a0 + (t >> 32);
+    let r2 = t & LO;
+    let t = m3 * a0 + (t >> 32);
+    let (r3, r4) = (t & LO, t >> 32);
+    // plus `m * a1`, one limb up
+    let t = m0 * a1 + r1;
+    let t = m1 * a1 + r2 + (t >> 32);
+    let t = m2 * a1 + r3 + (t >> 32);
+    m3 * a1 + r4 + (t >> 32)
+}
+
+/// Divides `src` by the divisor into `dst`, and returns `len`. If `MOD` is true, the remainders
+/// are computed instead.
+///
+/// Without AVX2, the two 64-bit lanes of SSE2 are slower than the scalar 64x64 -> 128 bit
+/// multiplications, so this is only used when AVX2 is available.
+///
+/// # Safety
+/// `src` must be valid for `len` reads and `dst` for `len` writes. They may be equal, but must not
+/// otherwise overlap. The CPU must support AVX2.
+#[target_feature(enable = "avx2")]
+unsafe fn div_u64_avx2<const MOD: bool>(
+    src: *const u64,
+    dst: *mut u64,
+    len: usize,
+    m: u128,
+    d: u64,
+) -> usize {
+    let op = |n: u64| {
+        let q = fastdiv_u64_limbs(n, m);
+        if MOD {
+            n.wrapping_sub(q.wrapping_mul(d))
+        } else {
+            q
+        }
+    };
+    // separate slices, so that the loops vectorize without runtime aliasing checks
+    if core::ptr::eq(src, dst) {
+        let values = core::slice::from_raw_parts_mut(dst, len);
+        values.iter_mut().for_each(|value| *value = op(*value));
+    } else {
+        let src = core::slice::from_raw_parts(src, len);
+        let dst = core::slice::from_raw_parts_mut(dst, len);
+        for (dst, &src) in dst.iter_mut().zip(src) {
+            *dst = op(src);
+        }
+    }
+    len
+}
+
+// computes `((m + 1) * n) >> 64` for zero-extended `u32` lanes in `n`. unsafe rather than a safe
+// `#[target_feature]` fn, which would need Rust 1.86
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn mulhi_avx2(n: __m256i, m_lo: __m256i, m_hi: __m256i) -> __m256i {
+    let bottom_half = _mm256_srli_epi64(_mm256_add_epi64(_mm256_mul_epu32(m_lo, n), n), 32);
+    _mm256_srli_epi64(_mm256_add_epi64(_mm256_mul_epu32(m_hi, n), bottom_half), 32)
+}
+
+/// Divides the first elements of `src` by the divisor into `dst`, 8 at a time, and returns the
+/// number of elements processed. If `MOD` is true, the remainders are computed instead.
+///
+/// # Safety
+/// `src` must be valid for `len` reads and `dst` for `len` writes. They may be equal, but must not
+/// otherwise overlap. The CPU must support AVX2.
+#[target_feature(enable = "avx2")]
+unsafe fn div_avx2<const MOD: bool>(
+    src: *const u32,
+    dst: *mut u32,
+    len: usize,
+    m: u64,
+    d: u32,
+) -> usize {
+    let m_lo = _mm256_set1_epi64x((m & 0xFFFFFFFF) as i64);
+    let m_hi = _mm256_set1_epi64x((m >> 32) as i64);
+    let low_halves = _mm256_set1_epi64x(0xFFFFFFFF);
+    let d = _mm256_set1_epi32(d as i32);
+
+    let mut i = 0;
+    while i + 8 <= len {
+        let n = _mm256_loadu_si256(src.add(i) as *const __m256i);
+        let q_even = mulhi_avx2(_mm256_and_si256(n, low_halves), m_lo, m_hi);
+        let q_odd = mulhi_avx2(_mm256_srli_epi64(n, 32), m_lo, m_hi);
+        let q = _mm256_blend_epi32(q_even, _mm256_slli_epi64(q_odd, 32), 0b10101010);
+        let out = if MOD {
+            _mm256_sub_epi32(n, _mm256_mullo_epi32(q, d))
+        } else {
+            q
+        };
+        _mm256_storeu_si256(dst.add(i) as *mut __m256i, out);
+        i += 8;
+    }
+    i
+}
+
+// computes `((m + 1) * n) >> 64` for zero-extended `u32` lanes in `n`
+#[cfg(feature = "avx512")]
+#[clippy::msrv = "1.89"]
+#[inline]
+#[target_feature(enable = "avx512f")]
+unsafe fn mulhi_avx512(n: __m512i, m_lo: __m512i, m_hi: __m512i) -> __m512i {
+    let bottom_half = _mm512_srli_epi64(_mm512_add_epi64(_mm512_mul_epu32(m_lo, n), n), 32);
+    _mm512_srli_epi64(_mm512_add_epi64(_mm512_mul_epu32(m_hi, n), bottom_half), 32)
+}
+
+/// Divides the first elements of `src` by the divisor into `dst`, 16 at a time, and returns the
+/// number of elements processed. If `MOD` is true, the remainders are computed instead.
+///
+/// # Safety
+/// `src` must be valid for `len` reads and `dst` for `len` writes. They may be equal, but must not
+/// otherwise overlap. The CPU must support AVX-512F.
+#[cfg(feature = "avx512")]
+#[clippy::msrv = "1.89"]
+#[target_feature(enable = "avx512f")]
+unsafe fn div_avx512<const MOD: bool>(
+    src: *const u32,
+    dst: *mut u32,
+    len: usize,
+    m: u64,
+    d: u32,
+) -> usize {
+    let m_lo = _mm512_set1_epi64((m & 0xFFFFFFFF) as i64);
+    let m_hi = _mm512_set1_epi64((m >> 32) as i64);
+    let low_halves = _mm512_set1_epi64(0xFFFFFFFF);
+    let d = _mm512_set1_epi32(d as i32);
+
+    let mut i = 0;
+    while i + 16 <= len {
+        let n = _mm512_loadu_si512(src.add(i) as *const __m512i);
+        let q_even = mulhi_avx512(_mm512_and_si512(n, low_halves), m_lo, m_hi);
+        let q_odd = mulhi_avx512(_mm512_srli_epi64(n, 32), m_lo, m_hi);
+        let q = _mm512_mask_blend_epi32(0xAAAA, q_even, _mm512_slli_epi64(q_odd, 32));
+        let out = if MOD {
+            _mm512_sub_epi32(n, _mm512_mullo_epi32(q, d))
+        } else {
+            q
+        };
+        _mm512_storeu_si512(dst.add(i) as *mut __m512i, out);
+        i += 16;
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::xorshift;
+    use crate::FastDiv;
+
+    fn check(kernel: unsafe fn(*const u32, *mut u32, usize, u64, u32) -> usize, is_mod: bool) {
+        let mut rng = xorshift();
+        let mut next = || (rng() >> 32) as u32;
+        let mut src: Vec<u32> = (0..1000).chain([u32::MAX - 1, u32::MAX]).collect();
+        src.extend((0..1000).map(|_| next()));
+
+        let divisors = (1..300).chain((0..32).map(|k| 1 << k));
+        for d in divisors.chain((0..300).map(|_| next())).filter(|&d| d != 0) {
+            let p = d.precompute_div();
+            // odd lengths exercise the scalar tail in the caller
+            for len in [0, 7, 15, 17, src.len()] {
+                let mut dst = vec![0; len];
+                let done = unsafe { kernel(src.as_ptr(), dst.as_mut_ptr(), len, p.m, d) };
+                assert!(len - done < 16);
+                for (&n, &out) in src[..done].iter().zip(&dst[..done]) {
+                    if is_mod {
+                        assert_eq!(out, n.fast_mod(p, d));
+                    } else {
+                        assert_eq!(out, n.fast_div(p));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn avx2() {
+        if is_x86_feature_detected!("avx2") {
+            check(div_avx2::<false>, false);
+            check(div_avx2::<true>, true);
+        }
+    }
+
+    #[test]
+    fn avx2_u64() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+        let mut next = xorshift();
+        let mut src: Vec<u64> = (0..1000).chain([u64::MAX - 1, u64::MAX]).collect();
+        src.extend((0..1000).map(|_| next()));
+
+        let divisors = (1..300).chain((0..64).map(|k| 1 << k));
+        for d in divisors.chain((0..300).map(|_| (next() >> (next() % 64)).max(1))) {
+            let p = d.precompute_div();
+            for len in [0, 7, src.len()] {
+                let mut dst = vec![0; len];
+                unsafe { div_u64_avx2::<false>(src.as_ptr(), dst.as_mut_ptr(), len, p.m, d) };
+                for (&n, &q) in src.iter().zip(&dst) {
+                    assert_eq!(q, n / d);
+                }
+                // in place
+                let mut values = src[..len].to_vec();
+                let ptr = values.as_mut_ptr();
+                unsafe { div_u64_avx2::<true>(ptr, ptr, len, p.m, d) };
+                for (&n, &r) in src.iter().zip(&values) {
+                    assert_eq!(r, n % d);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "avx512")]
+    #[test]
+    fn avx512() {
+        if is_x86_feature_detected!("avx512f") {
+            check(div_avx512::<false>, false);
+            check(div_avx512::<true>, true);
+        }
+    }
+}